@@ -1,5 +1,7 @@
 mod cli_arguments;
+mod integrator_type;
 mod rendering_mode;
 
 pub use cli_arguments::CliArguments;
+pub use integrator_type::IntegratorType;
 pub use rendering_mode::RenderingMode;
@@ -1,5 +1,6 @@
-use crate::cli::RenderingMode;
+use crate::cli::{IntegratorType, RenderingMode};
 use clap::Parser;
+use ray_tracer::composites::PathTracer;
 
 #[derive(Clone, Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -8,6 +9,18 @@ pub struct CliArguments {
     pub image_output_path: String,
     #[arg(value_enum, short, long, default_value_t = RenderingMode::Parallel)]
     pub rendering_mode: RenderingMode,
+    /// Number of samples per pixel used for anti-aliasing, overrides value from scene
+    #[arg(long, short)]
+    pub samples: Option<u32>,
+    /// Method of computing light, path tracing needs many samples per pixel to converge
+    #[arg(value_enum, short, long, default_value_t = IntegratorType::Whitted)]
+    pub integrator: IntegratorType,
+    /// Seed of random paths used by path tracing
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Maximal number of bounces of path used by path tracing
+    #[arg(long, default_value_t = PathTracer::DEFAULT_MAX_DEPTH)]
+    pub max_depth: u8,
     #[arg(long, short, action)]
     pub quiet: bool,
 }
@@ -1,6 +1,7 @@
-use crate::cli::{CliArguments, RenderingMode};
+use crate::cli::{CliArguments, IntegratorType, RenderingMode};
 use crate::scene_loader::load_scene_description;
 use clap::Parser;
+use ray_tracer::composites::{Integrator, PathTracer};
 use std::error::Error;
 use std::time::Instant;
 
@@ -10,7 +11,17 @@ mod scenes;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = CliArguments::parse();
-    let (world, camera) = load_scene_description(&args.scene_path)?;
+    let (world, mut camera) = load_scene_description(&args.scene_path)?;
+    if let Some(samples) = args.samples {
+        camera.samples = samples;
+    }
+    camera.integrator = match args.integrator {
+        IntegratorType::Whitted => Integrator::Whitted,
+        IntegratorType::PathTracing => Integrator::PathTracing(PathTracer {
+            max_depth: args.max_depth,
+            seed: args.seed,
+        }),
+    };
     if !args.quiet {
         println!("Rendering image using scene at {}", args.scene_path);
     }
@@ -1,13 +1,26 @@
-use ray_tracer::composites::{Camera, Material, World};
-use ray_tracer::patterns::{CheckerPattern, GradientPattern, Pattern, RingPattern, StripePattern};
-use ray_tracer::primitives::{Color, Light, Point, Vector};
+use ray_tracer::composites::{
+    Background, Camera, Canvas, DiffuseModel, Material, Microfacet, Motion, ObjParser, Projection,
+    SamplingMode, ShadingModel, World,
+};
+use ray_tracer::patterns::{
+    BlendPattern, CheckerPattern, CubeMapPattern, GradientPattern, MarblePattern,
+    MultiGradientPattern, NestedPattern, NoisePerturbation, NormalMapPerturbation,
+    NormalPerturbation, Pattern, PerlinNoise, PerturbedPattern, RadialGradientPattern, RingPattern,
+    SolidPattern, StripePattern, TextureMapPattern, TurbulencePattern, UvAlignCheckPattern,
+    UvCheckerPattern, UvImagePattern, UvMapping, UvPattern, WoodPattern, WrapMode,
+};
+use ray_tracer::primitives::{
+    AreaLight, Attenuation, Color, DirectionalLight, Light, Point, PointLight, SpotLight, Vector,
+};
 use ray_tracer::primitives::{Transformation, transformations};
-use ray_tracer::shapes::{Cone, Cube, Cylinder, Plane, Sphere, Transform};
+use ray_tracer::shapes::{
+    Cone, Csg, CsgOperation, Cube, Cylinder, Group, Plane, Shape, Sphere, Transform,
+};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::num::ParseFloatError;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use yaml_rust::Yaml::BadValue;
 use yaml_rust::{Yaml, YamlLoader};
@@ -31,15 +44,17 @@ struct SceneParser {
     materials: HashMap<String, Material>,
     patterns: HashMap<String, Arc<dyn Pattern>>,
     transformations: HashMap<String, Transformation>,
+    directory: PathBuf,
 }
 
 impl SceneParser {
-    pub fn new() -> Self {
+    pub fn new(directory: &Path) -> Self {
         return Self {
             colors: HashMap::<String, Color>::new(),
             materials: HashMap::<String, Material>::new(),
             patterns: HashMap::<String, Arc<dyn Pattern>>::new(),
             transformations: HashMap::<String, Transformation>::new(),
+            directory: directory.to_path_buf(),
         };
     }
 
@@ -52,6 +67,9 @@ impl SceneParser {
                 } else if name.ends_with("-material") {
                     let material = self.parse_material(&entry)?;
                     self.materials.insert(name.clone(), material);
+                } else if name.ends_with("-pattern") {
+                    let pattern = self.parse_pattern(&entry)?;
+                    self.patterns.insert(name.clone(), pattern);
                 } else if name.ends_with("-transform") || name.ends_with("-object") {
                     let transformation = self.parse_transformation(&entry)?;
                     self.transformations.insert(name.clone(), transformation);
@@ -113,6 +131,14 @@ impl SceneParser {
             material.pattern = Some(self.parse_pattern(&yaml["pattern"])?);
         }
 
+        if yaml["bump"] != BadValue {
+            material.normal_perturbation = Some(self.parse_normal_perturbation(&yaml["bump"])?);
+        }
+
+        if yaml["emissive"] != BadValue {
+            material.emissive = self.parse_color(&yaml["emissive"])?;
+        }
+
         if yaml["ambient"] != BadValue {
             material.ambient = parse_f64(&yaml["ambient"])?;
         }
@@ -145,50 +171,241 @@ impl SceneParser {
             material.casts_shadow = value;
         }
 
+        if yaml["absorption-color"] != BadValue {
+            material.absorption_color = self.parse_color(&yaml["absorption-color"])?;
+        }
+
+        if yaml["absorption-density"] != BadValue {
+            material.absorption_density = parse_f64(&yaml["absorption-density"])?;
+        }
+
+        if yaml["roughness"] != BadValue {
+            material.roughness = parse_f64(&yaml["roughness"])?;
+        }
+
+        if yaml["glossy-samples"] != BadValue {
+            material.glossy_samples = parse_count(&yaml["glossy-samples"], 0)?;
+        }
+
+        if yaml["shading"] != BadValue {
+            material.shading_model = parse_shading_model(&yaml["shading"])?;
+        }
+
         return Ok(material);
     }
 
+    /// Parses pattern, entries of `colors` may be patterns themselves, in which case pattern
+    /// only lays them out, see [NestedPattern]
     fn parse_pattern(&self, yaml: &Yaml) -> Result<Arc<dyn Pattern>, Box<dyn Error>> {
-        let colors = yaml["colors"].as_vec().unwrap();
-        let color_a = self.parse_color(&colors[0])?;
-        let color_b = self.parse_color(&colors[1])?;
-        let maybe_transformation = match yaml[Keyword::TRANSFORMATION] {
-            BadValue => None,
-            _ => Some(self.parse_transformation(&yaml[Keyword::TRANSFORMATION])?),
+        if let Yaml::String(name) = yaml {
+            return match self.patterns.get(name) {
+                Some(pattern) => Ok(pattern.clone()),
+                None => Err(format!("Undefined pattern: {name}").into()),
+            };
+        }
+        let yaml = if yaml[Keyword::VALUE] != BadValue {
+            &yaml[Keyword::VALUE]
+        } else {
+            yaml
         };
-        return match &yaml[Keyword::TYPE] {
-            Yaml::String(value) => match value.as_str() {
-                "stripes" => {
-                    let mut pattern = StripePattern::new(color_a, color_b);
-                    if let Some(transformation) = maybe_transformation {
-                        pattern.set_transformation(transformation);
-                    }
-                    Ok(Arc::new(pattern))
+        let kind = yaml[Keyword::TYPE].as_str().ok_or("Missing pattern type")?;
+        let mut pattern: Box<dyn Pattern> = match kind {
+            "map" | "image" => self.parse_texture_map(yaml)?,
+            "perturbed" => Box::new(PerturbedPattern::new(
+                self.parse_pattern(&yaml["pattern"])?,
+                parse_noise(yaml)?,
+                parse_f64(&yaml["scale"])?,
+            )),
+            "blend" => {
+                let patterns = yaml["patterns"]
+                    .as_vec()
+                    .ok_or("Missing blended patterns")?;
+                if patterns.len() != 2 {
+                    return Err("Blend needs exactly 2 patterns".into());
                 }
-                "gradient" => {
-                    let mut pattern = GradientPattern::new(color_a, color_b);
-                    if let Some(transformation) = maybe_transformation {
-                        pattern.set_transformation(transformation);
-                    }
-                    Ok(Arc::new(pattern))
+                let mut pattern = BlendPattern::new(
+                    self.parse_pattern(&patterns[0])?,
+                    self.parse_pattern(&patterns[1])?,
+                );
+                if yaml["weight"] != BadValue {
+                    pattern.weight = parse_f64(&yaml["weight"])?;
                 }
-                "rings" => {
-                    let mut pattern = RingPattern::new(color_a, color_b);
-                    if let Some(transformation) = maybe_transformation {
-                        pattern.set_transformation(transformation);
-                    }
-                    Ok(Arc::new(pattern))
+                Box::new(pattern)
+            }
+            "multi-gradient" => {
+                let stops = yaml["stops"].as_vec().ok_or("Missing gradient stops")?;
+                let stops = stops
+                    .iter()
+                    .map(|stop| Ok((parse_f64(&stop[0])?, self.parse_color(&stop[1])?)))
+                    .collect::<Result<Vec<(f64, Color)>, Box<dyn Error>>>()?;
+                Box::new(MultiGradientPattern::new(stops))
+            }
+            _ => {
+                let colors = yaml["colors"].as_vec().ok_or("Missing pattern colors")?;
+                if colors.len() != 2 {
+                    return Err("Pattern needs exactly 2 colors".into());
                 }
-                "checkers" => {
-                    let mut pattern = CheckerPattern::new(color_a, color_b);
-                    if let Some(transformation) = maybe_transformation {
-                        pattern.set_transformation(transformation);
-                    }
-                    Ok(Arc::new(pattern))
+                if colors.iter().any(|entry| self.is_pattern(entry)) {
+                    let layout =
+                        self.parse_two_color_pattern(kind, Color::BLACK, Color::WHITE, yaml)?;
+                    Box::new(NestedPattern::new(
+                        Arc::from(layout),
+                        self.parse_color_or_pattern(&colors[0])?,
+                        self.parse_color_or_pattern(&colors[1])?,
+                    ))
+                } else {
+                    let color_a = self.parse_color(&colors[0])?;
+                    let color_b = self.parse_color(&colors[1])?;
+                    self.parse_two_color_pattern(kind, color_a, color_b, yaml)?
+                }
+            }
+        };
+        if yaml[Keyword::TRANSFORMATION] != BadValue {
+            pattern.set_transformation(self.parse_transformation(&yaml[Keyword::TRANSFORMATION])?);
+        }
+        return Ok(Arc::from(pattern));
+    }
+
+    /// Returns true if entry of `colors` describes pattern rather than color
+    fn is_pattern(&self, yaml: &Yaml) -> bool {
+        return match yaml {
+            Yaml::Hash(_) => yaml[Keyword::TYPE] != BadValue,
+            Yaml::String(name) => self.patterns.contains_key(name),
+            _ => false,
+        };
+    }
+
+    fn parse_color_or_pattern(&self, yaml: &Yaml) -> Result<Arc<dyn Pattern>, Box<dyn Error>> {
+        if self.is_pattern(yaml) {
+            return self.parse_pattern(yaml);
+        }
+        return Ok(Arc::new(SolidPattern::new(self.parse_color(yaml)?)));
+    }
+
+    /// Parses image loaded from `file` relative to scene directory
+    fn parse_image(&self, yaml: &Yaml) -> Result<UvImagePattern, Box<dyn Error>> {
+        let file = yaml["file"].as_str().ok_or("Missing image file")?;
+        let mut pattern = UvImagePattern::new(Canvas::from_image_file(self.directory.join(file))?);
+        pattern.wrap_mode = match yaml["wrap"].as_str() {
+            None | Some("wrap") => WrapMode::Wrap,
+            Some("clamp") => WrapMode::Clamp,
+            Some(name) => return Err(format!("Incorrect wrap mode: {name}").into()),
+        };
+        return Ok(pattern);
+    }
+
+    /// Parses perturbation of normals, either bumps made by noise or normal map image
+    fn parse_normal_perturbation(
+        &self,
+        yaml: &Yaml,
+    ) -> Result<Arc<dyn NormalPerturbation>, Box<dyn Error>> {
+        let mut perturbation: Box<dyn NormalPerturbation> = match yaml[Keyword::TYPE].as_str() {
+            Some("noise") => Box::new(NoisePerturbation::new(
+                parse_noise(yaml)?,
+                parse_f64(&yaml["scale"])?,
+            )),
+            Some("normal-map") => {
+                let mut perturbation = NormalMapPerturbation::new(
+                    Arc::new(self.parse_image(yaml)?),
+                    parse_uv_mapping(&yaml["mapping"])?,
+                );
+                if yaml["strength"] != BadValue {
+                    perturbation.strength = parse_f64(&yaml["strength"])?;
+                }
+                Box::new(perturbation)
+            }
+            _ => return Err("Incorrect bump type".into()),
+        };
+        if yaml[Keyword::TRANSFORMATION] != BadValue {
+            perturbation
+                .set_transformation(self.parse_transformation(&yaml[Keyword::TRANSFORMATION])?);
+        }
+        return Ok(Arc::from(perturbation));
+    }
+
+    fn parse_two_color_pattern(
+        &self,
+        kind: &str,
+        color_a: Color,
+        color_b: Color,
+        yaml: &Yaml,
+    ) -> Result<Box<dyn Pattern>, Box<dyn Error>> {
+        return match kind {
+            "stripes" => Ok(Box::new(StripePattern::new(color_a, color_b))),
+            "gradient" => Ok(Box::new(GradientPattern::new(color_a, color_b))),
+            "radial-gradient" => Ok(Box::new(RadialGradientPattern::new(color_a, color_b))),
+            "rings" => Ok(Box::new(RingPattern::new(color_a, color_b))),
+            "checkers" => Ok(Box::new(CheckerPattern::new(color_a, color_b))),
+            "marble" => {
+                let mut pattern = MarblePattern::new(color_a, color_b, parse_noise(yaml)?);
+                if yaml["distortion"] != BadValue {
+                    pattern.distortion = parse_f64(&yaml["distortion"])?;
+                }
+                if yaml["octaves"] != BadValue {
+                    pattern.octaves = parse_count(&yaml["octaves"], 0)?;
+                }
+                Ok(Box::new(pattern))
+            }
+            "wood" => {
+                let mut pattern = WoodPattern::new(color_a, color_b, parse_noise(yaml)?);
+                if yaml["distortion"] != BadValue {
+                    pattern.distortion = parse_f64(&yaml["distortion"])?;
                 }
-                _ => panic!("Incorrect pattern type"),
-            },
-            _ => panic!("Incorrect pattern type"),
+                Ok(Box::new(pattern))
+            }
+            "turbulence" => {
+                let mut pattern = TurbulencePattern::new(color_a, color_b, parse_noise(yaml)?);
+                if yaml["octaves"] != BadValue {
+                    pattern.octaves = parse_count(&yaml["octaves"], 0)?;
+                }
+                Ok(Box::new(pattern))
+            }
+            _ => Err(format!("Incorrect pattern type: {kind}").into()),
+        };
+    }
+
+    /// Parses pattern which maps [UvPattern] onto shape, cube mapping takes separate pattern
+    /// for each face while other mappings take single one, image may be given directly instead
+    /// of in `uv-pattern`
+    fn parse_texture_map(&self, yaml: &Yaml) -> Result<Box<dyn Pattern>, Box<dyn Error>> {
+        let mapping = parse_uv_mapping(&yaml["mapping"])?;
+        if mapping == UvMapping::Cube && yaml["uv-pattern"] == BadValue {
+            let faces = [
+                self.parse_uv_pattern(&yaml["left"])?,
+                self.parse_uv_pattern(&yaml["front"])?,
+                self.parse_uv_pattern(&yaml["right"])?,
+                self.parse_uv_pattern(&yaml["back"])?,
+                self.parse_uv_pattern(&yaml["up"])?,
+                self.parse_uv_pattern(&yaml["down"])?,
+            ];
+            return Ok(Box::new(CubeMapPattern::new(faces)));
+        }
+        let uv_pattern = if yaml[Keyword::TYPE].as_str() == Some("image") {
+            self.parse_uv_pattern(yaml)?
+        } else {
+            self.parse_uv_pattern(&yaml["uv-pattern"])?
+        };
+        return Ok(Box::new(TextureMapPattern::new(uv_pattern, mapping)));
+    }
+
+    fn parse_uv_pattern(&self, yaml: &Yaml) -> Result<Arc<dyn UvPattern>, Box<dyn Error>> {
+        let colors = &yaml["colors"];
+        return match yaml[Keyword::TYPE].as_str() {
+            Some("checkers") => Ok(Arc::new(UvCheckerPattern::new(
+                parse_f64(&yaml["width"])?,
+                parse_f64(&yaml["height"])?,
+                self.parse_color(&colors[0])?,
+                self.parse_color(&colors[1])?,
+            ))),
+            Some("align-check") => Ok(Arc::new(UvAlignCheckPattern::new(
+                self.parse_color(&colors["main"])?,
+                self.parse_color(&colors["upper-left"])?,
+                self.parse_color(&colors["upper-right"])?,
+                self.parse_color(&colors["bottom-left"])?,
+                self.parse_color(&colors["bottom-right"])?,
+            ))),
+            Some("image") => Ok(Arc::new(self.parse_image(yaml)?)),
+            _ => Err("Incorrect uv pattern type".into()),
         };
     }
 
@@ -246,6 +463,217 @@ impl SceneParser {
         return Ok((material, transformation));
     }
 
+    fn parse_shape(&self, yaml: &Yaml) -> Result<Option<Box<dyn Shape>>, Box<dyn Error>> {
+        let Some(mut shape) = self.create_shape(yaml)? else {
+            return Ok(None);
+        };
+        if yaml["motion"] != BadValue {
+            let motion = parse_motion(&yaml["motion"], shape.as_ref())?;
+            shape.set_motion(motion);
+        }
+        return Ok(Some(shape));
+    }
+
+    fn create_shape(&self, yaml: &Yaml) -> Result<Option<Box<dyn Shape>>, Box<dyn Error>> {
+        let Yaml::String(name) = &yaml[Keyword::ADD] else {
+            return Ok(None);
+        };
+        return match name.as_str() {
+            "plane" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                Ok(Some(Box::new(Plane::new(material, transformation))))
+            }
+            "sphere" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                Ok(Some(Box::new(Sphere::new(material, transformation))))
+            }
+            "cube" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                Ok(Some(Box::new(Cube::new(material, transformation))))
+            }
+            "cone" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                let mut cone = Cone::default();
+                cone.material = material;
+                cone.set_transformation(transformation);
+                if let Yaml::Boolean(value) = yaml["closed"] {
+                    cone.closed = value;
+                }
+                if yaml["max"] != BadValue {
+                    cone.max = parse_f64(&yaml["max"])?;
+                }
+                if yaml["min"] != BadValue {
+                    cone.min = parse_f64(&yaml["min"])?;
+                }
+                Ok(Some(Box::new(cone)))
+            }
+            "cylinder" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                let mut cylinder = Cylinder::default();
+                cylinder.material = material;
+                cylinder.set_transformation(transformation);
+                if let Yaml::Boolean(value) = yaml["closed"] {
+                    cylinder.closed = value;
+                }
+                if yaml["max"] != BadValue {
+                    cylinder.max = parse_f64(&yaml["max"])?;
+                }
+                if yaml["min"] != BadValue {
+                    cylinder.min = parse_f64(&yaml["min"])?;
+                }
+                Ok(Some(Box::new(cylinder)))
+            }
+            "group" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                let mut group = Group::new(material, transformation);
+                if let Yaml::Array(children) = &yaml["children"] {
+                    for child in children {
+                        let child = with_inherited_material(child, &yaml[Keyword::MATERIAL]);
+                        if let Some(shape) = self.parse_shape(&child)? {
+                            group.add_child(shape);
+                        }
+                    }
+                }
+                Ok(Some(Box::new(group)))
+            }
+            "obj" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                let file = yaml["file"].as_str().ok_or("Missing obj file path")?;
+                let obj_parser = ObjParser::from_file(self.directory.join(file))?;
+                let mut group = obj_parser.to_group(&material);
+                group.set_transformation(transformation);
+                Ok(Some(Box::new(group)))
+            }
+            "csg" => {
+                let (material, transformation) = self.parse_material_and_transformation(yaml)?;
+                let operation = match yaml["operation"].as_str() {
+                    Some("union") => CsgOperation::Union,
+                    Some("intersection") => CsgOperation::Intersection,
+                    Some("difference") => CsgOperation::Difference,
+                    _ => return Err("Incorrect csg operation".into()),
+                };
+                let left = self
+                    .parse_shape(&yaml["left"])?
+                    .ok_or("Missing left csg operand")?;
+                let right = self
+                    .parse_shape(&yaml["right"])?
+                    .ok_or("Missing right csg operand")?;
+                Ok(Some(Box::new(Csg::new(
+                    material,
+                    transformation,
+                    operation,
+                    left,
+                    right,
+                ))))
+            }
+            _ => Ok(None),
+        };
+    }
+
+    /// Parses light of given `type`, point light when type is missing, keys which do not belong
+    /// to the type are rejected so that misspelled or misplaced keys are not silently ignored
+    fn parse_light(&self, yaml: &Yaml) -> Result<Box<dyn Light>, Box<dyn Error>> {
+        let kind = match &yaml[Keyword::TYPE] {
+            BadValue => "point",
+            Yaml::String(kind) => kind.as_str(),
+            _ => return Err("Incorrect light type".into()),
+        };
+        let keys: &[&str] = match kind {
+            "point" => &["at", "intensity", "attenuation"],
+            "area" => &[
+                "corner",
+                "uvec",
+                "usteps",
+                "vvec",
+                "vsteps",
+                "jitter",
+                "intensity",
+                "attenuation",
+            ],
+            "spot" => &[
+                "at",
+                "direction",
+                "angle",
+                "soft-edge",
+                "intensity",
+                "attenuation",
+            ],
+            "directional" => &["direction", "intensity"],
+            _ => return Err(format!("Incorrect light type: {kind}").into()),
+        };
+        if let Yaml::Hash(entries) = yaml {
+            for key in entries.keys() {
+                let key = key.as_str().unwrap_or_default();
+                if key != Keyword::ADD && key != Keyword::TYPE && !keys.contains(&key) {
+                    return Err(format!("Unknown key of {kind} light: {key}").into());
+                }
+            }
+        }
+
+        let intensity = Color::from(parse_required_array_of_3(yaml, "intensity")?);
+        let attenuation = parse_attenuation(&yaml["attenuation"])?;
+        match kind {
+            "directional" => {
+                let direction = Vector::from(parse_required_array_of_3(yaml, "direction")?);
+                return Ok(Box::new(DirectionalLight::new(direction, intensity)));
+            }
+            "spot" => {
+                let position = Point::from(parse_required_array_of_3(yaml, "at")?);
+                let direction = Vector::from(parse_required_array_of_3(yaml, "direction")?);
+                let cone_angle = parse_f64(&yaml["angle"])?;
+                let soft_edge = match yaml["soft-edge"] {
+                    BadValue => 0.0,
+                    _ => parse_f64(&yaml["soft-edge"])?,
+                };
+                let mut light =
+                    SpotLight::new(position, direction, cone_angle, soft_edge, intensity);
+                light.attenuation = attenuation;
+                return Ok(Box::new(light));
+            }
+            "area" => {
+                let corner = Point::from(parse_required_array_of_3(yaml, "corner")?);
+                let u_vector = Vector::from(parse_required_array_of_3(yaml, "uvec")?);
+                let v_vector = Vector::from(parse_required_array_of_3(yaml, "vvec")?);
+                let u_steps = parse_count(&yaml["usteps"], 1)? as usize;
+                let v_steps = parse_count(&yaml["vsteps"], 1)? as usize;
+                let mut light =
+                    AreaLight::new(corner, u_vector, u_steps, v_vector, v_steps, intensity);
+                if let Yaml::Boolean(value) = yaml["jitter"] {
+                    light.jitter = value;
+                }
+                light.attenuation = attenuation;
+                return Ok(Box::new(light));
+            }
+            _ => {
+                let position = Point::from(parse_required_array_of_3(yaml, "at")?);
+                let mut light = PointLight::new(position, intensity);
+                light.attenuation = attenuation;
+                return Ok(Box::new(light));
+            }
+        }
+    }
+
+    /// Parses color of rays missing all shapes, either solid `color`, sky gradient from `bottom`
+    /// to `top` or environment map given by `pattern` which is looked up by ray direction
+    fn parse_background(&self, yaml: &Yaml) -> Result<Background, Box<dyn Error>> {
+        if yaml["pattern"] != BadValue {
+            return Ok(Background::Pattern(self.parse_pattern(&yaml["pattern"])?));
+        }
+        if yaml["top"] != BadValue || yaml["bottom"] != BadValue {
+            if yaml["top"] == BadValue || yaml["bottom"] == BadValue {
+                return Err("Gradient background needs both top and bottom color".into());
+            }
+            return Ok(Background::Gradient {
+                bottom: self.parse_color(&yaml["bottom"])?,
+                top: self.parse_color(&yaml["top"])?,
+            });
+        }
+        if yaml[Keyword::COLOR] != BadValue {
+            return Ok(Background::Color(self.parse_color(&yaml[Keyword::COLOR])?));
+        }
+        return Err("Missing background color, gradient or pattern".into());
+    }
+
     fn parse_scene(&self, yaml: &Yaml) -> Result<(World, Camera), Box<dyn Error>> {
         let mut world = World::new(Vec::new(), Vec::new());
         let mut camera = Camera::new(0, 0, 0);
@@ -255,86 +683,88 @@ impl SceneParser {
                     "camera" => {
                         let horizontal_size = parse_f64(&entry["width"])? as u32;
                         let vertical_size = parse_f64(&entry["height"])? as u32;
-                        let fov = parse_f64(&entry["field-of-view"])?;
+                        let projection = parse_projection(&entry)?;
+                        // orthographic and equirectangular projections do not need field of view
+                        let fov = match projection {
+                            Projection::Perspective | Projection::Fisheye => {
+                                parse_f64(&entry["field-of-view"])?
+                            }
+                            _ => parse_f64(&entry["field-of-view"]).unwrap_or_default(),
+                        };
                         let from = parse_array_of_3(entry["from"].as_vec().unwrap())?;
                         let from = Point::from(from);
                         let to = parse_array_of_3(entry["to"].as_vec().unwrap())?;
                         let to = Point::from(to);
                         let up = parse_array_of_3(entry["up"].as_vec().unwrap())?;
                         let up = Vector::from(up);
-                        camera = Camera::new(horizontal_size, vertical_size, fov);
+                        camera = Camera::with_projection(
+                            horizontal_size,
+                            vertical_size,
+                            fov,
+                            projection,
+                        );
                         camera.set_transformation(transformations::view_transform(from, to, up));
-                    }
-                    "light" => {
-                        let position = parse_array_of_3(entry["at"].as_vec().unwrap())?;
-                        let position = Point::new(position[0], position[1], position[2]);
-                        let intensity = parse_array_of_3(entry["intensity"].as_vec().unwrap())?;
-                        let intensity = Color::new(intensity[0], intensity[1], intensity[2]);
-                        world.lights.push(Light::new(position, intensity));
-                    }
-                    "plane" => {
-                        let (material, transformation) =
-                            self.parse_material_and_transformation(&entry)?;
-                        world
-                            .shapes
-                            .push(Box::new(Plane::new(material, transformation)));
-                    }
-                    "sphere" => {
-                        let (material, transformation) =
-                            self.parse_material_and_transformation(&entry)?;
-                        world
-                            .shapes
-                            .push(Box::new(Sphere::new(material, transformation)));
-                    }
-                    "cube" => {
-                        let (material, transformation) =
-                            self.parse_material_and_transformation(&entry)?;
-                        world
-                            .shapes
-                            .push(Box::new(Cube::new(material, transformation)));
-                    }
-                    "cone" => {
-                        let (material, transformation) =
-                            self.parse_material_and_transformation(&entry)?;
-                        let mut cone = Cone::default();
-                        cone.material = material;
-                        cone.set_transformation(transformation);
-                        if let Yaml::Boolean(value) = entry["closed"] {
-                            cone.closed = value;
+                        if entry["samples"] != BadValue {
+                            camera.samples = parse_count(&entry["samples"], 1)?;
                         }
-                        if entry["max"] != BadValue {
-                            cone.max = parse_f64(&entry["max"])?;
+                        if let Yaml::String(sampling_mode) = &entry["sampling"] {
+                            camera.sampling_mode = parse_sampling_mode(sampling_mode)?;
                         }
-                        if entry["min"] != BadValue {
-                            cone.min = parse_f64(&entry["min"])?;
+                        // without explicit focal distance camera focuses at the point it looks at
+                        if entry["aperture"] != BadValue {
+                            camera.aperture = parse_f64(&entry["aperture"])?;
+                            camera.focal_distance = (to - from).magnitude();
                         }
-                        world.shapes.push(Box::new(cone));
-                    }
-                    "cylinder" => {
-                        let (material, transformation) =
-                            self.parse_material_and_transformation(&entry)?;
-                        let mut cylinder = Cylinder::default();
-                        cylinder.material = material;
-                        cylinder.set_transformation(transformation);
-                        if let Yaml::Boolean(value) = entry["closed"] {
-                            cylinder.closed = value;
+                        if entry["focal-distance"] != BadValue {
+                            camera.focal_distance = parse_f64(&entry["focal-distance"])?;
                         }
-                        if entry["max"] != BadValue {
-                            cylinder.max = parse_f64(&entry["max"])?;
+                        if entry["shutter-open"] != BadValue {
+                            camera.shutter_open = parse_shutter_time(&entry["shutter-open"])?;
                         }
-                        if entry["min"] != BadValue {
-                            cylinder.min = parse_f64(&entry["min"])?;
+                        if entry["shutter-close"] != BadValue {
+                            camera.shutter_close = parse_shutter_time(&entry["shutter-close"])?;
+                        }
+                    }
+                    "light" => {
+                        world.lights.push(self.parse_light(&entry)?);
+                    }
+                    "background" => {
+                        world.background = self.parse_background(&entry)?;
+                    }
+                    _ => {
+                        if let Some(shape) = self.parse_shape(&entry)? {
+                            world.add_shape(shape);
                         }
-                        world.shapes.push(Box::new(cylinder));
                     }
-                    _ => (),
                 }
             }
         }
+        world.build_bvh();
         return Ok((world, camera));
     }
 }
 
+fn parse_required_array_of_3(yaml: &Yaml, key: &str) -> Result<[f64; 3], Box<dyn Error>> {
+    let Some(values) = yaml[key].as_vec() else {
+        return Err(format!("Missing or incorrect {key}").into());
+    };
+    return Ok(parse_array_of_3(values)?);
+}
+
+/// Returns copy of shape description which uses given material when it has none of its own,
+/// so material of group is passed down to its children
+fn with_inherited_material(yaml: &Yaml, material: &Yaml) -> Yaml {
+    let mut yaml = yaml.clone();
+    if let Yaml::Hash(entries) = &mut yaml
+        && *material != BadValue
+    {
+        entries
+            .entry(Yaml::String(Keyword::MATERIAL.to_string()))
+            .or_insert_with(|| material.clone());
+    }
+    return yaml;
+}
+
 fn parse_f64(yaml: &Yaml) -> Result<f64, ParseFloatError> {
     match yaml {
         Yaml::Integer(value) => Ok(*value as f64),
@@ -343,6 +773,18 @@ fn parse_f64(yaml: &Yaml) -> Result<f64, ParseFloatError> {
     }
 }
 
+/// Parses whole number of samples, steps or octaves, values which are fractional or smaller than
+/// `minimum` are rejected instead of being truncated
+fn parse_count(yaml: &Yaml, minimum: u32) -> Result<u32, Box<dyn Error>> {
+    let value = parse_f64(yaml)?;
+    if value.fract() != 0.0 || value < f64::from(minimum) || value > f64::from(u32::MAX) {
+        return Err(
+            format!("Incorrect count: {value}, expected whole number from {minimum}").into(),
+        );
+    }
+    return Ok(value as u32);
+}
+
 fn parse_array_of_3(slice: &[Yaml]) -> Result<[f64; 3], ParseFloatError> {
     let values = {
         let values: Result<Vec<_>, _> = slice.iter().take(3).map(parse_f64).collect();
@@ -351,6 +793,137 @@ fn parse_array_of_3(slice: &[Yaml]) -> Result<[f64; 3], ParseFloatError> {
     return Ok([values[0], values[1], values[2]]);
 }
 
+/// Parses motion of shape, by default shape rotates around its origin
+fn parse_motion(yaml: &Yaml, shape: &dyn Shape) -> Result<Motion, Box<dyn Error>> {
+    let parse_vector = |key: &str| -> Result<Vector, Box<dyn Error>> {
+        return match yaml[key].as_vec() {
+            Some(values) => Ok(Vector::from(parse_array_of_3(values)?)),
+            None => Ok(Vector::ZERO),
+        };
+    };
+    let pivot = match yaml["pivot"].as_vec() {
+        Some(values) => Point::from(parse_array_of_3(values)?),
+        None => shape.transformation() * Point::ORIGIN,
+    };
+    return Ok(Motion::new(
+        parse_vector("velocity")?,
+        parse_vector("angular-velocity")?,
+        pivot,
+    ));
+}
+
+/// Parses time of opening or closing shutter, which has to be within range covered by bounds of
+/// moving shapes
+fn parse_shutter_time(yaml: &Yaml) -> Result<f64, Box<dyn Error>> {
+    let time = parse_f64(yaml)?;
+    if !(0.0..=Motion::END_TIME).contains(&time) {
+        return Err(format!("Shutter time must be in range [0, {}]", Motion::END_TIME).into());
+    }
+    return Ok(time);
+}
+
+fn parse_sampling_mode(name: &str) -> Result<SamplingMode, Box<dyn Error>> {
+    return match name {
+        "grid" => Ok(SamplingMode::Grid),
+        "jittered" => Ok(SamplingMode::Jittered),
+        "stratified" => Ok(SamplingMode::Stratified),
+        _ => Err(format!("Incorrect sampling mode: {name}").into()),
+    };
+}
+
+fn parse_uv_mapping(yaml: &Yaml) -> Result<UvMapping, Box<dyn Error>> {
+    return match yaml.as_str() {
+        Some("spherical") => Ok(UvMapping::Spherical),
+        Some("planar") => Ok(UvMapping::Planar),
+        Some("cylindrical") => Ok(UvMapping::Cylindrical),
+        Some("cube") => Ok(UvMapping::Cube),
+        _ => Err("Incorrect texture mapping".into()),
+    };
+}
+
+/// Parses noise of procedural pattern, seed defaults to 0
+fn parse_noise(yaml: &Yaml) -> Result<PerlinNoise, Box<dyn Error>> {
+    return match &yaml["seed"] {
+        BadValue => Ok(PerlinNoise::default()),
+        Yaml::Integer(seed) => Ok(PerlinNoise::new(u64::try_from(*seed)?)),
+        _ => Err("Seed of noise must be non negative integer".into()),
+    };
+}
+
+/// Parses shading model given either by name or as mapping with type, microfacet model takes
+/// metallic and roughness in range [0, 1] and diffuse model
+fn parse_shading_model(yaml: &Yaml) -> Result<ShadingModel, Box<dyn Error>> {
+    let kind = match yaml {
+        Yaml::String(name) => name.as_str(),
+        _ => yaml["type"]
+            .as_str()
+            .ok_or("Shading model must have type")?,
+    };
+    return match kind {
+        "phong" => Ok(ShadingModel::Phong),
+        "microfacet" => {
+            let mut microfacet = Microfacet::default();
+            if yaml["metallic"] != BadValue {
+                microfacet.metallic = parse_f64(&yaml["metallic"])?;
+            }
+            if yaml["roughness"] != BadValue {
+                microfacet.roughness = parse_f64(&yaml["roughness"])?;
+            }
+            if !(0.0..=1.0).contains(&microfacet.metallic)
+                || !(0.0..=1.0).contains(&microfacet.roughness)
+            {
+                return Err("Metallic and roughness must be in range [0, 1]".into());
+            }
+            microfacet.diffuse_model = match yaml["diffuse"].as_str() {
+                None | Some("lambert") => DiffuseModel::Lambert,
+                Some("oren-nayar") => DiffuseModel::OrenNayar,
+                Some(name) => return Err(format!("Incorrect diffuse model: {name}").into()),
+            };
+            Ok(ShadingModel::Microfacet(microfacet))
+        }
+        _ => Err(format!("Incorrect shading model: {kind}").into()),
+    };
+}
+
+/// Parses projection of camera, orthographic projection needs width of view
+fn parse_projection(yaml: &Yaml) -> Result<Projection, Box<dyn Error>> {
+    return match &yaml["projection"] {
+        BadValue => Ok(Projection::Perspective),
+        Yaml::String(name) => match name.as_str() {
+            "perspective" => Ok(Projection::Perspective),
+            "orthographic" => Ok(Projection::Orthographic {
+                width: parse_f64(&yaml["view-width"])?,
+            }),
+            "fisheye" => Ok(Projection::Fisheye),
+            "equirectangular" => Ok(Projection::Equirectangular),
+            _ => Err(format!("Incorrect projection: {name}").into()),
+        },
+        _ => Err("Incorrect projection".into()),
+    };
+}
+
+/// Parses attenuation given either by name or as array of constant, linear and quadratic factors
+fn parse_attenuation(yaml: &Yaml) -> Result<Attenuation, Box<dyn Error>> {
+    return match yaml {
+        BadValue | Yaml::Null => Ok(Attenuation::NONE),
+        Yaml::String(value) => match value.as_str() {
+            "none" => Ok(Attenuation::NONE),
+            "inverse-square" => Ok(Attenuation::INVERSE_SQUARE),
+            _ => Err(format!("Incorrect attenuation: {value}").into()),
+        },
+        Yaml::Array(values) => {
+            let [constant, linear, quadratic] = parse_array_of_3(values)?;
+            if !Attenuation::are_valid_coefficients(constant, linear, quadratic) {
+                return Err(
+                    "Attenuation coefficients must be non-negative and not all zero".into(),
+                );
+            }
+            Ok(Attenuation::new(constant, linear, quadratic))
+        }
+        _ => Err("Incorrect attenuation".into()),
+    };
+}
+
 fn load_file_to_yaml<P: AsRef<Path>>(path: P) -> Result<Yaml, Box<dyn Error>> {
     let file = fs::read_to_string(path)?;
     let mut docs = YamlLoader::load_from_str(&file)?;
@@ -359,8 +932,13 @@ fn load_file_to_yaml<P: AsRef<Path>>(path: P) -> Result<Yaml, Box<dyn Error>> {
 }
 
 pub fn load_scene_description<P: AsRef<Path>>(path: P) -> Result<(World, Camera), Box<dyn Error>> {
+    let directory = path
+        .as_ref()
+        .parent()
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
     let yaml = load_file_to_yaml(path)?;
-    let mut scene_parser = SceneParser::new();
+    let mut scene_parser = SceneParser::new(&directory);
 
     scene_parser.process_definitions(&yaml)?;
     return scene_parser.parse_scene(&yaml);
@@ -396,4 +974,491 @@ mod tests {
         let value = parse_array_of_3(yaml.as_vec().unwrap()).unwrap();
         assert_eq!(value, expected);
     }
+
+    #[rstest]
+    #[case("~", Attenuation::NONE)]
+    #[case("none", Attenuation::NONE)]
+    #[case("inverse-square", Attenuation::INVERSE_SQUARE)]
+    #[case("[1, 0.5, 0.25]", Attenuation::new(1.0, 0.5, 0.25))]
+    fn parse_attenuation_from_yaml(#[case] string: &str, #[case] expected: Attenuation) {
+        let yaml = parse_yaml(string);
+        assert_eq!(parse_attenuation(&yaml).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("grid", SamplingMode::Grid)]
+    #[case("jittered", SamplingMode::Jittered)]
+    #[case("stratified", SamplingMode::Stratified)]
+    fn parse_sampling_mode_from_name(#[case] name: &str, #[case] expected: SamplingMode) {
+        assert_eq!(parse_sampling_mode(name).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_incorrect_sampling_mode() {
+        assert!(parse_sampling_mode("random").is_err());
+    }
+
+    #[rstest]
+    #[case("samples: -1")]
+    #[case("samples: 0")]
+    #[case("samples: 2.5")]
+    fn parse_camera_with_incorrect_samples(#[case] samples: &str) {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml(&format!(
+            "- add: camera\n  width: 4\n  height: 4\n  field-of-view: 1\n  \
+             from: [0, 0, -5]\n  to: [0, 0, 0]\n  up: [0, 1, 0]\n  {samples}"
+        ));
+        assert!(parser.parse_scene(&yaml).is_err());
+    }
+
+    #[rstest]
+    #[case("{}", Projection::Perspective)]
+    #[case("projection: fisheye", Projection::Fisheye)]
+    #[case("projection: equirectangular", Projection::Equirectangular)]
+    #[case(
+        "projection: orthographic\nview-width: 8",
+        Projection::Orthographic { width: 8.0 }
+    )]
+    fn parse_projection_from_yaml(#[case] string: &str, #[case] expected: Projection) {
+        let yaml = parse_yaml(string);
+        assert_eq!(parse_projection(&yaml).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_incorrect_projection() {
+        assert!(parse_projection(&parse_yaml("projection: orthographic")).is_err());
+        assert!(parse_projection(&parse_yaml("projection: cylindrical")).is_err());
+    }
+
+    #[test]
+    fn children_of_group_inherit_its_material() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml(
+            "
+add: group
+material: {color: [1, 0, 0]}
+children:
+  - add: sphere
+  - add: sphere
+    material: {color: [0, 0, 1]}
+  - add: group
+    children:
+      - add: sphere
+",
+        );
+        let shape = parser.parse_shape(&yaml).unwrap().unwrap();
+        let red = Material {
+            color: Color::new(1, 0, 0),
+            ..Material::default()
+        };
+        let blue = Material {
+            color: Color::new(0, 0, 1),
+            ..Material::default()
+        };
+        let mut inner_group = Group::new(red.clone(), Transformation::IDENTITY);
+        inner_group.add_child(Box::new(Sphere::new(red.clone(), Transformation::IDENTITY)));
+        let mut expected = Group::new(red.clone(), Transformation::IDENTITY);
+        expected.add_child(Box::new(Sphere::new(red, Transformation::IDENTITY)));
+        expected.add_child(Box::new(Sphere::new(blue, Transformation::IDENTITY)));
+        expected.add_child(Box::new(inner_group));
+        assert!(shape.as_ref() == &expected as &dyn Shape);
+    }
+
+    #[rstest]
+    #[case("add: csg\noperation: xor\nleft: {add: sphere}\nright: {add: cube}")]
+    #[case("add: csg\noperation: union\nright: {add: cube}")]
+    #[case("add: csg\noperation: union\nleft: {add: sphere}")]
+    #[case("add: obj")]
+    fn parse_incorrect_shape(#[case] string: &str) {
+        let parser = SceneParser::new(Path::new("."));
+        assert!(parser.parse_shape(&parse_yaml(string)).is_err());
+    }
+
+    #[test]
+    fn parse_lights_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("add: light\nat: [1, 2, 3]\nintensity: [1, 1, 1]");
+        let light = parser.parse_light(&yaml).unwrap();
+        let expected = PointLight::new(Point::new(1, 2, 3), Color::WHITE);
+        assert!(light.as_ref() == &expected as &dyn Light);
+        let yaml = parse_yaml(
+            "
+add: light
+type: area
+corner: [-1, 2, 4]
+uvec: [2, 0, 0]
+usteps: 4
+vvec: [0, 2, 0]
+vsteps: 2
+intensity: [1, 1, 1]
+",
+        );
+        let light = parser.parse_light(&yaml).unwrap();
+        let expected = AreaLight::new(
+            Point::new(-1, 2, 4),
+            Vector::new(2, 0, 0),
+            4,
+            Vector::new(0, 2, 0),
+            2,
+            Color::WHITE,
+        );
+        assert!(light.as_ref() == &expected as &dyn Light);
+    }
+
+    #[rstest]
+    #[case("add: light\ncorner: [-1, 2, 4]\nat: [0, 0, 0]\nintensity: [1, 1, 1]")]
+    #[case("add: light\ntype: area\ncorner: [-1, 2, 4]\nintensity: [1, 1, 1]")]
+    #[case("add: light\ntype: sun\nat: [0, 0, 0]\nintensity: [1, 1, 1]")]
+    #[case("add: light\nintensity: [1, 1, 1]")]
+    #[case(
+        "add: light\ntype: directional\ndirection: [0, -1, 0]\nintensity: [1, 1, 1]\n\
+        attenuation: inverse-square"
+    )]
+    #[case(
+        "add: light\ntype: area\ncorner: [-1, 2, 4]\nuvec: [2, 0, 0]\nusteps: 0\n\
+        vvec: [0, 2, 0]\nvsteps: 2\nintensity: [1, 1, 1]"
+    )]
+    #[case(
+        "add: light\ntype: area\ncorner: [-1, 2, 4]\nuvec: [2, 0, 0]\nusteps: 4\n\
+        vvec: [0, 2, 0]\nvsteps: 1.5\nintensity: [1, 1, 1]"
+    )]
+    fn parse_incorrect_light(#[case] string: &str) {
+        let parser = SceneParser::new(Path::new("."));
+        assert!(parser.parse_light(&parse_yaml(string)).is_err());
+    }
+
+    #[rstest]
+    #[case("2", 1, 2)]
+    #[case("0", 0, 0)]
+    #[case("16.0", 1, 16)]
+    fn parse_count_from_yaml(#[case] string: &str, #[case] minimum: u32, #[case] expected: u32) {
+        assert_eq!(parse_count(&parse_yaml(string), minimum).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("0", 1)]
+    #[case("-4", 0)]
+    #[case("2.7", 0)]
+    #[case("many", 0)]
+    fn parse_incorrect_count(#[case] string: &str, #[case] minimum: u32) {
+        assert!(parse_count(&parse_yaml(string), minimum).is_err());
+    }
+
+    #[test]
+    fn parse_texture_map_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml(
+            "type: map\nmapping: spherical\n\
+             uv-pattern: {type: checkers, width: 16, height: 8, colors: [[0, 0, 0], [1, 1, 1]]}",
+        );
+        let pattern = parser.parse_pattern(&yaml).unwrap();
+        let checkers = UvCheckerPattern::new(16, 8, Color::BLACK, Color::WHITE);
+        let expected: Box<dyn Pattern> = Box::new(TextureMapPattern::new(
+            Arc::new(checkers),
+            UvMapping::Spherical,
+        ));
+        assert_eq!(pattern.as_ref(), expected.as_ref());
+        let yaml = parse_yaml("type: map\nmapping: cube\nleft: {type: checkers}");
+        assert!(parser.parse_pattern(&yaml).is_err());
+    }
+
+    /// File in temporary directory named after process and test, so that concurrent test runs
+    /// do not share it, file is removed when test ends even if it fails
+    struct TemporaryFile {
+        directory: PathBuf,
+        name: String,
+    }
+
+    impl TemporaryFile {
+        fn new(test_name: &str) -> Self {
+            return Self {
+                directory: std::env::temp_dir(),
+                name: format!("ray_tracer_{}_{test_name}.png", std::process::id()),
+            };
+        }
+
+        fn path(&self) -> PathBuf {
+            return self.directory.join(&self.name);
+        }
+    }
+
+    impl Drop for TemporaryFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(self.path());
+        }
+    }
+
+    #[test]
+    fn parse_image_pattern_from_yaml() {
+        let file = TemporaryFile::new("parse_image_pattern_from_yaml");
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixel(1, 0, Color::RED);
+        canvas.to_png_file(file.path()).unwrap();
+        let parser = SceneParser::new(&file.directory);
+        let yaml = parse_yaml(&format!(
+            "type: image\nfile: {}\nmapping: planar\nwrap: clamp",
+            file.name
+        ));
+        let pattern = parser.parse_pattern(&yaml);
+        let mut image = UvImagePattern::new(canvas);
+        image.wrap_mode = WrapMode::Clamp;
+        let expected: Box<dyn Pattern> =
+            Box::new(TextureMapPattern::new(Arc::new(image), UvMapping::Planar));
+        assert_eq!(pattern.unwrap().as_ref(), expected.as_ref());
+        let yaml = parse_yaml("type: image\nfile: missing.png\nmapping: planar");
+        assert!(parser.parse_pattern(&yaml).is_err());
+    }
+
+    #[test]
+    fn parse_noise_patterns_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml(
+            "type: perturbed\nscale: 0.2\nseed: 4\n\
+             pattern: {type: stripes, colors: [[1, 1, 1], [0, 0, 0]]}",
+        );
+        let expected: Box<dyn Pattern> = Box::new(PerturbedPattern::new(
+            Arc::new(StripePattern::new(Color::WHITE, Color::BLACK)),
+            PerlinNoise::new(4),
+            0.2,
+        ));
+        assert_eq!(
+            parser.parse_pattern(&yaml).unwrap().as_ref(),
+            expected.as_ref()
+        );
+
+        let yaml = parse_yaml("type: marble\noctaves: 2\ncolors: [[1, 1, 1], [0, 0, 0]]");
+        let mut marble = MarblePattern::new(Color::WHITE, Color::BLACK, PerlinNoise::default());
+        marble.octaves = 2;
+        let expected: Box<dyn Pattern> = Box::new(marble);
+        assert_eq!(
+            parser.parse_pattern(&yaml).unwrap().as_ref(),
+            expected.as_ref()
+        );
+
+        let yaml = parse_yaml("type: wood\nseed: -1\ncolors: [[1, 1, 1], [0, 0, 0]]");
+        assert!(parser.parse_pattern(&yaml).is_err());
+        let yaml = parse_yaml("type: marble\noctaves: -1\ncolors: [[1, 1, 1], [0, 0, 0]]");
+        assert!(parser.parse_pattern(&yaml).is_err());
+        let yaml = parse_yaml("type: turbulence\noctaves: 2.5\ncolors: [[1, 1, 1], [0, 0, 0]]");
+        assert!(parser.parse_pattern(&yaml).is_err());
+    }
+
+    #[test]
+    fn parse_nested_patterns_from_yaml() {
+        let mut parser = SceneParser::new(Path::new("."));
+        let definitions = parse_yaml(
+            "- define: red-stripes-pattern\n  \
+               value: {type: stripes, colors: [[1, 0, 0], [1, 1, 1]]}",
+        );
+        parser.process_definitions(&definitions).unwrap();
+        let yaml = parse_yaml(
+            "type: checkers\n\
+             colors:\n\
+             - red-stripes-pattern\n\
+             - type: blend\n\
+             \x20 weight: 0.25\n\
+             \x20 patterns:\n\
+             \x20 - red-stripes-pattern\n\
+             \x20 - {type: radial-gradient, colors: [[0, 0, 0], [0, 0, 1]]}",
+        );
+        let stripes: Arc<dyn Pattern> = Arc::new(StripePattern::new(Color::RED, Color::WHITE));
+        let mut blend = BlendPattern::new(
+            stripes.clone(),
+            Arc::new(RadialGradientPattern::new(Color::BLACK, Color::BLUE)),
+        );
+        blend.weight = 0.25;
+        let expected: Box<dyn Pattern> = Box::new(NestedPattern::new(
+            Arc::new(CheckerPattern::new(Color::BLACK, Color::WHITE)),
+            stripes,
+            Arc::new(blend),
+        ));
+        assert_eq!(
+            parser.parse_pattern(&yaml).unwrap().as_ref(),
+            expected.as_ref()
+        );
+
+        let yaml = parse_yaml(
+            "type: rings\ncolors: [[0, 1, 0], {type: gradient, colors: [[0, 0, 0], [1, 1, 1]]}]",
+        );
+        let expected: Box<dyn Pattern> = Box::new(NestedPattern::new(
+            Arc::new(RingPattern::new(Color::BLACK, Color::WHITE)),
+            Arc::new(SolidPattern::new(Color::GREEN)),
+            Arc::new(GradientPattern::new(Color::BLACK, Color::WHITE)),
+        ));
+        assert_eq!(
+            parser.parse_pattern(&yaml).unwrap().as_ref(),
+            expected.as_ref()
+        );
+    }
+
+    #[test]
+    fn parse_multi_gradient_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("type: multi-gradient\nstops: [[0, [1, 0, 0]], [0.5, [0, 1, 0]]]");
+        let expected: Box<dyn Pattern> = Box::new(MultiGradientPattern::new(vec![
+            (0.0, Color::RED),
+            (0.5, Color::GREEN),
+        ]));
+        assert_eq!(
+            parser.parse_pattern(&yaml).unwrap().as_ref(),
+            expected.as_ref()
+        );
+        assert!(
+            parser
+                .parse_pattern(&parse_yaml("type: spiral\ncolors: [[0, 0, 0], [1, 1, 1]]"))
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_pattern(&parse_yaml("type: blend\npatterns: []"))
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_pattern(&parse_yaml("undefined-pattern"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_bump_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("bump: {type: noise, scale: 0.3, seed: 2}");
+        let material = parser.parse_material(&yaml).unwrap();
+        let expected: Arc<dyn NormalPerturbation> =
+            Arc::new(NoisePerturbation::new(PerlinNoise::new(2), 0.3));
+        assert_eq!(material.normal_perturbation, Some(expected));
+        let yaml = parse_yaml("bump: {type: normal-map, mapping: planar}");
+        assert!(parser.parse_material(&yaml).is_err());
+        let yaml = parse_yaml("bump: {type: bricks}");
+        assert!(parser.parse_material(&yaml).is_err());
+    }
+
+    #[test]
+    fn parse_emissive_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("emissive: [4, 2, 1]");
+        let material = parser.parse_material(&yaml).unwrap();
+        assert_eq!(material.emissive, Color::new(4, 2, 1));
+        let yaml = parse_yaml("color: [1, 0, 0]");
+        let material = parser.parse_material(&yaml).unwrap();
+        assert_eq!(material.emissive, Color::BLACK);
+    }
+
+    #[test]
+    fn parse_absorption_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("absorption-color: [0.2, 0.8, 0.5]\nabsorption-density: 2");
+        let material = parser.parse_material(&yaml).unwrap();
+        assert_eq!(material.absorption_color, Color::new(0.2, 0.8, 0.5));
+        assert_eq!(material.absorption_density, 2.0);
+    }
+
+    #[test]
+    fn parse_roughness_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("roughness: 0.3\nglossy-samples: 16");
+        let material = parser.parse_material(&yaml).unwrap();
+        assert_eq!(material.roughness, 0.3);
+        assert_eq!(material.glossy_samples, 16);
+        let yaml = parse_yaml("roughness: 0.3\nglossy-samples: -4");
+        assert!(parser.parse_material(&yaml).is_err());
+        let yaml = parse_yaml("roughness: 0.3\nglossy-samples: 2.7");
+        assert!(parser.parse_material(&yaml).is_err());
+    }
+
+    #[rstest]
+    #[case("phong", ShadingModel::Phong)]
+    #[case("{type: microfacet}", ShadingModel::Microfacet(Microfacet::default()))]
+    #[case(
+        "{type: microfacet, metallic: 1, roughness: 0.2}",
+        ShadingModel::Microfacet(Microfacet::new(1, 0.2))
+    )]
+    #[case(
+        "{type: microfacet, diffuse: oren-nayar}",
+        ShadingModel::Microfacet(Microfacet {
+            diffuse_model: DiffuseModel::OrenNayar,
+            ..Default::default()
+        })
+    )]
+    fn parse_shading_model_from_yaml(#[case] string: &str, #[case] expected: ShadingModel) {
+        let yaml = parse_yaml(string);
+        assert_eq!(parse_shading_model(&yaml).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("blinn")]
+    #[case("{type: microfacet, roughness: 2}")]
+    #[case("{type: microfacet, diffuse: minnaert}")]
+    fn parse_incorrect_shading_model(#[case] string: &str) {
+        assert!(parse_shading_model(&parse_yaml(string)).is_err());
+    }
+
+    #[test]
+    fn parse_motion_from_yaml() {
+        let sphere = Sphere::new(Material::default(), transformations::translation(1, 2, 3));
+        let yaml = parse_yaml("velocity: [1, 0, 0]\nangular-velocity: [0, 2, 0]");
+        let motion = parse_motion(&yaml, &sphere).unwrap();
+        assert_eq!(
+            motion,
+            Motion::new(Vector::RIGHT, Vector::new(0, 2, 0), Point::new(1, 2, 3))
+        );
+        let yaml = parse_yaml("velocity: [1, 0, 0]\npivot: [0, 0, 0]");
+        let motion = parse_motion(&yaml, &sphere).unwrap();
+        assert_eq!(
+            motion,
+            Motion::new(Vector::RIGHT, Vector::ZERO, Point::ORIGIN)
+        );
+    }
+
+    #[rstest]
+    #[case("0", Some(0.0))]
+    #[case("0.5", Some(0.5))]
+    #[case("1", Some(1.0))]
+    #[case("1.5", None)]
+    #[case("-0.5", None)]
+    fn parse_shutter_time_from_yaml(#[case] string: &str, #[case] expected: Option<f64>) {
+        assert_eq!(parse_shutter_time(&parse_yaml(string)).ok(), expected);
+    }
+
+    #[test]
+    fn parse_background_from_yaml() {
+        let parser = SceneParser::new(Path::new("."));
+        let yaml = parse_yaml("color: [0.2, 0.4, 0.6]");
+        assert_eq!(
+            parser.parse_background(&yaml).unwrap(),
+            Background::Color(Color::new(0.2, 0.4, 0.6))
+        );
+        let yaml = parse_yaml("bottom: [1, 1, 1]\ntop: [0, 0, 1]");
+        assert_eq!(
+            parser.parse_background(&yaml).unwrap(),
+            Background::Gradient {
+                bottom: Color::WHITE,
+                top: Color::BLUE
+            }
+        );
+        let yaml = parse_yaml("pattern: {type: checkers, colors: [[1, 1, 1], [0, 0, 0]]}");
+        let expected: Arc<dyn Pattern> = Arc::new(CheckerPattern::new(Color::WHITE, Color::BLACK));
+        assert_eq!(
+            parser.parse_background(&yaml).unwrap(),
+            Background::Pattern(expected)
+        );
+        assert!(
+            parser
+                .parse_background(&parse_yaml("top: [0, 0, 1]"))
+                .is_err()
+        );
+        assert!(
+            parser
+                .parse_background(&parse_yaml("image: sky.png"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_incorrect_attenuation() {
+        assert!(parse_attenuation(&parse_yaml("quartic")).is_err());
+        assert!(parse_attenuation(&parse_yaml("[1, -0.5, 0]")).is_err());
+        assert!(parse_attenuation(&parse_yaml("[0, 0, 0]")).is_err());
+    }
 }
@@ -1,17 +1,41 @@
+pub use background::Background;
+pub use bounding_box::BoundingBox;
+pub use bounding_volume_hierarchy::BoundingVolumeHierarchy;
 pub use camera::Camera;
 pub use canvas::Canvas;
 pub use computed_hit::ComputedHit;
+pub use diffuse_model::DiffuseModel;
+pub use integrator::Integrator;
 pub use intersection::Intersection;
 pub use intersections::Intersections;
 pub use material::Material;
+pub use microfacet::Microfacet;
+pub use motion::Motion;
+pub use obj_parser::ObjParser;
+pub use path_tracer::PathTracer;
+pub use projection::Projection;
 pub use ray::Ray;
+pub use sampling_mode::SamplingMode;
+pub use shading_model::ShadingModel;
 pub use world::World;
 
+mod background;
+mod bounding_box;
+mod bounding_volume_hierarchy;
 mod camera;
 mod canvas;
 mod computed_hit;
+mod diffuse_model;
+mod integrator;
 mod intersection;
 mod intersections;
 mod material;
+mod microfacet;
+mod motion;
+mod obj_parser;
+mod path_tracer;
+mod projection;
 mod ray;
+mod sampling_mode;
+mod shading_model;
 mod world;
@@ -0,0 +1,97 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Vector};
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Color of light arriving along rays which miss every shape in
+/// [World](crate::composites::World)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Background {
+    /// Same color in every direction
+    Color(Color),
+    /// Vertical sky gradient, color changes linearly from straight down to straight up
+    Gradient { bottom: Color, top: Color },
+    /// Environment map, direction of ray is turned into point on unit sphere around origin
+    /// which is looked up in [Pattern], so transformation of [Pattern] rotates environment
+    Pattern(Arc<dyn Pattern>),
+}
+
+impl Background {
+    pub fn color_at(&self, direction: &Vector) -> Color {
+        return match self {
+            Self::Color(color) => *color,
+            Self::Gradient { bottom, top } => {
+                let fraction = (direction.normalized().y + 1.0) / 2.0;
+                *bottom + (*top - *bottom) * fraction
+            }
+            Self::Pattern(pattern) => {
+                pattern.color_at_nested(&(Point::ORIGIN + direction.normalized()))
+            }
+        };
+    }
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        return Self::Color(Color::BLACK);
+    }
+}
+
+impl Display for Background {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return match self {
+            Self::Color(color) => write!(formatter, "{color}"),
+            Self::Gradient { bottom, top } => formatter
+                .debug_struct("Gradient")
+                .field("bottom", bottom)
+                .field("top", top)
+                .finish(),
+            Self::Pattern(pattern) => write!(formatter, "{pattern}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::TestPattern;
+    use crate::primitives::transformations;
+    use crate::shapes::Transform;
+    use crate::utils::CoarseEq;
+    use rstest::rstest;
+
+    #[test]
+    fn solid_background_is_same_in_every_direction() {
+        let background = Background::Color(Color::RED);
+        assert_eq!(background.color_at(&Vector::UP), Color::RED);
+        assert_eq!(background.color_at(&Vector::new(1, -2, 3)), Color::RED);
+    }
+
+    #[rstest]
+    #[case(Vector::DOWN, Color::WHITE)]
+    #[case(Vector::UP, Color::BLUE)]
+    #[case(Vector::new(0, 0, -4), Color::new(0.5, 0.5, 1))]
+    #[case(
+        Vector::new(0, 1, 1),
+        Color::new(0.1464466094067262, 0.1464466094067262, 1)
+    )]
+    fn gradient_background_depends_on_elevation(
+        #[case] direction: Vector,
+        #[case] expected: Color,
+    ) {
+        let background = Background::Gradient {
+            bottom: Color::WHITE,
+            top: Color::BLUE,
+        };
+        assert!(background.color_at(&direction).coarse_eq(&expected));
+    }
+
+    #[test]
+    fn pattern_background_is_looked_up_by_direction() {
+        let mut pattern = TestPattern::new();
+        pattern.set_transformation(transformations::scaling(2, 2, 2));
+        let background = Background::Pattern(Arc::new(pattern));
+        let color = background.color_at(&Vector::new(0, 0, 3));
+        assert!(color.coarse_eq(&Color::new(0, 0, 0.5)));
+    }
+}
@@ -0,0 +1,328 @@
+use crate::composites::Ray;
+use crate::consts::{MAX, MIN};
+use crate::primitives::{Point, Transformation};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+/// Axis aligned box used for culling rays before testing them against shapes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// Box containing no points, neutral element of [BoundingBox::merge]
+    pub const EMPTY: Self = Self {
+        min: Point {
+            x: f64::INFINITY,
+            y: f64::INFINITY,
+            z: f64::INFINITY,
+        },
+        max: Point {
+            x: f64::NEG_INFINITY,
+            y: f64::NEG_INFINITY,
+            z: f64::NEG_INFINITY,
+        },
+    };
+
+    /// Box containing every point
+    pub const INFINITE: Self = Self {
+        min: Point {
+            x: f64::NEG_INFINITY,
+            y: f64::NEG_INFINITY,
+            z: f64::NEG_INFINITY,
+        },
+        max: Point {
+            x: f64::INFINITY,
+            y: f64::INFINITY,
+            z: f64::INFINITY,
+        },
+    };
+
+    pub const fn new(min: Point, max: Point) -> Self {
+        return Self { min, max };
+    }
+
+    /// Maps [MIN] used by shapes as missing lower limit to negative infinity
+    pub fn unbounded_min(value: f64) -> f64 {
+        return if value <= MIN {
+            f64::NEG_INFINITY
+        } else {
+            value
+        };
+    }
+
+    /// Maps [MAX] used by shapes as missing upper limit to positive infinity
+    pub fn unbounded_max(value: f64) -> f64 {
+        return if value >= MAX { f64::INFINITY } else { value };
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Self {
+        let mut bounding_box = Self::EMPTY;
+        for point in points {
+            bounding_box.add_point(point);
+        }
+        return bounding_box;
+    }
+
+    pub fn add_point(&mut self, point: Point) {
+        self.min = Point::new(
+            self.min.x.min(point.x),
+            self.min.y.min(point.y),
+            self.min.z.min(point.z),
+        );
+        self.max = Point::new(
+            self.max.x.max(point.x),
+            self.max.y.max(point.y),
+            self.max.z.max(point.z),
+        );
+    }
+
+    pub fn merge(&self, rhs: &Self) -> Self {
+        let mut result = *self;
+        result.add_point(rhs.min);
+        result.add_point(rhs.max);
+        return result;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z;
+    }
+
+    /// Returns whether box has finite extent along every axis
+    pub fn is_bounded(&self) -> bool {
+        return !self.is_empty()
+            && self
+                .min
+                .values()
+                .into_iter()
+                .chain(self.max.values())
+                .all(f64::is_finite);
+    }
+
+    pub fn centroid(&self) -> Point {
+        return Point::new(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+            (self.min.z + self.max.z) / 2.0,
+        );
+    }
+
+    /// Returns index of the axis along which box is the longest
+    pub fn longest_axis(&self) -> usize {
+        let extent = self.max - self.min;
+        if extent.x >= extent.y && extent.x >= extent.z {
+            return 0;
+        } else if extent.y >= extent.z {
+            return 1;
+        }
+        return 2;
+    }
+
+    /// Returns box grown by given margin on every side
+    pub fn padded(&self, margin: f64) -> Self {
+        return Self::new(
+            self.min.map(|value| value - margin),
+            self.max.map(|value| value + margin),
+        );
+    }
+
+    /// Returns box enclosing this box after applying [Transformation] to it, infinite extents
+    /// are carried over only to axes which they contribute to
+    pub fn transform(&self, transformation: &Transformation) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        let mut min = [0.0; 3];
+        let mut max = [0.0; 3];
+        for row in 0..3 {
+            min[row] = transformation[row][3];
+            max[row] = transformation[row][3];
+            for column in 0..3 {
+                let factor = transformation[row][column];
+                if factor == 0.0 {
+                    continue;
+                }
+                let value_1 = factor * self.min[column];
+                let value_2 = factor * self.max[column];
+                min[row] += value_1.min(value_2);
+                max[row] += value_1.max(value_2);
+            }
+        }
+        return Self::new(
+            Point::new(min[0], min[1], min[2]),
+            Point::new(max[0], max[1], max[2]),
+        );
+    }
+
+    /// Returns distance from [Point] to the nearest point of the box, zero for points inside
+    pub fn distance_to(&self, point: Point) -> f64 {
+        if self.is_empty() {
+            return f64::INFINITY;
+        }
+        let nearest = Point::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        );
+        return (point - nearest).magnitude();
+    }
+
+    pub fn corners(&self) -> [Point; 8] {
+        let (min, max) = (self.min, self.max);
+        return [
+            Point::new(min.x, min.y, min.z),
+            Point::new(min.x, min.y, max.z),
+            Point::new(min.x, max.y, min.z),
+            Point::new(min.x, max.y, max.z),
+            Point::new(max.x, min.y, min.z),
+            Point::new(max.x, min.y, max.z),
+            Point::new(max.x, max.y, min.z),
+            Point::new(max.x, max.y, max.z),
+        ];
+    }
+
+    /// Returns whether the line along [Ray] crosses the box, intersections behind ray origin
+    /// are included as they still take part in resolving refractive indexes, direction parallel
+    /// to an axis gives infinite distances which keep or reject the ray by its origin alone
+    pub fn is_intersected_by(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let mut distance_min = f64::NEG_INFINITY;
+        let mut distance_max = f64::INFINITY;
+
+        for axis in 0..3 {
+            let origin = ray.origin[axis];
+            let direction_inverse = 1.0 / ray.direction[axis];
+            let mut distance_1 = (self.min[axis] - origin) * direction_inverse;
+            let mut distance_2 = (self.max[axis] - origin) * direction_inverse;
+            if distance_1 > distance_2 {
+                core::mem::swap(&mut distance_1, &mut distance_2);
+            }
+            distance_min = distance_min.max(distance_1);
+            distance_max = distance_max.min(distance_2);
+            if distance_min > distance_max {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        return Self::EMPTY;
+    }
+}
+
+impl Display for BoundingBox {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("BoundingBox")
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .finish();
+    }
+}
+
+impl CoarseEq for BoundingBox {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.min.coarse_eq(&rhs.min) && self.max.coarse_eq(&rhs.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::PI;
+    use crate::primitives::{Vector, transformations};
+    use rstest::rstest;
+
+    #[test]
+    fn empty_bounding_box() {
+        let bounding_box = BoundingBox::default();
+        assert!(bounding_box.is_empty());
+        assert!(!bounding_box.is_bounded());
+    }
+
+    #[test]
+    fn adding_points_to_bounding_box() {
+        let bounding_box = BoundingBox::from_points([Point::new(-5, 2, 0), Point::new(7, 0, -3)]);
+        assert_eq!(bounding_box.min, Point::new(-5, 0, -3));
+        assert_eq!(bounding_box.max, Point::new(7, 2, 0));
+        assert!(bounding_box.is_bounded());
+    }
+
+    #[test]
+    fn merging_bounding_boxes() {
+        let box_1 = BoundingBox::new(Point::new(-5, -2, 0), Point::new(7, 4, 4));
+        let box_2 = BoundingBox::new(Point::new(8, -7, -2), Point::new(14, 2, 8));
+        let merged = box_1.merge(&box_2);
+        assert_eq!(merged.min, Point::new(-5, -7, -2));
+        assert_eq!(merged.max, Point::new(14, 4, 8));
+        assert_eq!(BoundingBox::EMPTY.merge(&box_1), box_1);
+    }
+
+    #[test]
+    fn transforming_bounding_box() {
+        let bounding_box = BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+        let transformation =
+            transformations::rotation_x(PI / 4.0) * transformations::rotation_y(PI / 4.0);
+        let transformed = bounding_box.transform(&transformation);
+        let sqrt_2 = 2.0_f64.sqrt();
+        let expected = BoundingBox::new(
+            Point::new(-sqrt_2, -(1.0 + sqrt_2 / 2.0), -(1.0 + sqrt_2 / 2.0)),
+            Point::new(sqrt_2, 1.0 + sqrt_2 / 2.0, 1.0 + sqrt_2 / 2.0),
+        );
+        assert!(transformed.coarse_eq(&expected));
+    }
+
+    #[test]
+    fn transforming_infinite_bounding_box() {
+        let transformed = BoundingBox::INFINITE.transform(&transformations::translation(1, 2, 3));
+        assert_eq!(transformed, BoundingBox::INFINITE);
+        let plane_bounds = BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0, f64::INFINITY),
+        );
+        let transformed = plane_bounds.transform(&transformations::translation(0, 2, 0));
+        assert_eq!(transformed.min.y, 2.0);
+        assert_eq!(transformed.max.y, 2.0);
+        assert_eq!(transformed.min.x, f64::NEG_INFINITY);
+        let transformed = plane_bounds.transform(&transformations::rotation_z(PI / 2.0));
+        assert_eq!(transformed.min.y, f64::NEG_INFINITY);
+        assert_eq!(transformed.max.z, f64::INFINITY);
+    }
+
+    #[test]
+    fn distance_from_point_to_bounding_box() {
+        let bounding_box = BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+        assert_eq!(bounding_box.distance_to(Point::new(0.5, 0, 0)), 0.0);
+        assert_eq!(bounding_box.distance_to(Point::new(0, 3, 0)), 2.0);
+        assert_eq!(bounding_box.distance_to(Point::new(4, 5, 0)), 5.0);
+        assert_eq!(BoundingBox::EMPTY.distance_to(Point::ORIGIN), f64::INFINITY);
+    }
+
+    #[rstest]
+    #[case(Point::new(5, 0.5, 0), Vector::LEFT, true)]
+    #[case(Point::new(0.5, -5, 0), Vector::UP, true)]
+    #[case(Point::new(0, 0.5, 0), Vector::FORWARD, true)]
+    #[case(Point::new(0, 0, 5), Vector::FORWARD, true)]
+    #[case(Point::new(-2, 0, 0), Vector::new(2, 4, 6), false)]
+    #[case(Point::new(2, 0, 2), Vector::BACKWARD, false)]
+    #[case(Point::new(2, 2, 0), Vector::LEFT, false)]
+    #[case(Point::new(0, 2, -1e9), Vector::new(0, -2e-9, 1), true)]
+    #[case(Point::new(0, 2, -1e9), Vector::new(0, 0, 1), false)]
+    fn intersecting_ray_with_bounding_box(
+        #[case] origin: Point,
+        #[case] direction: Vector,
+        #[case] expected: bool,
+    ) {
+        let bounding_box = BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+        let ray = Ray::new(origin, direction.normalized());
+        assert_eq!(bounding_box.is_intersected_by(&ray), expected);
+    }
+}
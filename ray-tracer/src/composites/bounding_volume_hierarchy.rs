@@ -0,0 +1,191 @@
+use crate::composites::{BoundingBox, Intersections, Ray};
+use crate::consts::EPSILON;
+use crate::primitives::Point;
+use crate::shapes::Shape;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Leaf {
+        bounds: BoundingBox,
+        start: usize,
+        count: usize,
+    },
+    Branch {
+        bounds: BoundingBox,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    const fn bounds(&self) -> &BoundingBox {
+        return match self {
+            Self::Leaf { bounds, .. } | Self::Branch { bounds, .. } => bounds,
+        };
+    }
+}
+
+/// Tree of bounding boxes over shapes, built once and then used to skip shapes that a ray
+/// cannot possibly hit
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoundingVolumeHierarchy {
+    nodes: Vec<Node>,
+    shape_indices: Vec<usize>,
+    unbounded_shape_indices: Vec<usize>,
+}
+
+impl BoundingVolumeHierarchy {
+    pub const MAX_LEAF_SIZE: usize = 4;
+
+    pub fn new(shapes: &[Box<dyn Shape>]) -> Self {
+        let mut bvh = Self::default();
+        let mut entries: Vec<(usize, BoundingBox, Point)> = Vec::new();
+        for (index, shape) in shapes.iter().enumerate() {
+            let bounds = shape.bounds();
+            if bounds.is_bounded() {
+                let bounds = bounds.padded(EPSILON);
+                entries.push((index, bounds, bounds.centroid()));
+            } else {
+                bvh.unbounded_shape_indices.push(index);
+            }
+        }
+        if !entries.is_empty() {
+            bvh.build(&mut entries);
+        }
+        return bvh;
+    }
+
+    fn build(&mut self, entries: &mut [(usize, BoundingBox, Point)]) -> usize {
+        let bounds = entries
+            .iter()
+            .fold(BoundingBox::EMPTY, |acc, (_, bounds, _)| acc.merge(bounds));
+        let node_index = self.nodes.len();
+
+        if entries.len() <= Self::MAX_LEAF_SIZE {
+            let start = self.shape_indices.len();
+            self.shape_indices
+                .extend(entries.iter().map(|(index, _, _)| *index));
+            self.nodes.push(Node::Leaf {
+                bounds,
+                start,
+                count: entries.len(),
+            });
+            return node_index;
+        }
+
+        let axis = BoundingBox::from_points(entries.iter().map(|(_, _, centroid)| *centroid))
+            .longest_axis();
+        let middle = entries.len() / 2;
+        entries.select_nth_unstable_by(middle, |(_, _, centroid_1), (_, _, centroid_2)| {
+            return centroid_1[axis].total_cmp(&centroid_2[axis]);
+        });
+
+        // placeholder replaced once both children are built and their indices are known
+        self.nodes.push(Node::Leaf {
+            bounds,
+            start: 0,
+            count: 0,
+        });
+        let (left_entries, right_entries) = entries.split_at_mut(middle);
+        let left = self.build(left_entries);
+        let right = self.build(right_entries);
+        self.nodes[node_index] = Node::Branch {
+            bounds,
+            left,
+            right,
+        };
+        return node_index;
+    }
+
+    /// Intersects [Ray] with shapes whose bounds it crosses, shapes are visited in the order
+    /// they were given in, so the result is the same as testing every shape
+    pub fn intersect<'shapes>(
+        &self,
+        ray: &Ray,
+        shapes: &'shapes [Box<dyn Shape>],
+        intersections: &mut Intersections<'shapes>,
+    ) {
+        let mut candidates: Vec<usize> = Vec::new();
+        if !self.nodes.is_empty() {
+            let mut stack = vec![0];
+            while let Some(node_index) = stack.pop() {
+                let node = &self.nodes[node_index];
+                if !node.bounds().is_intersected_by(ray) {
+                    continue;
+                }
+                match node {
+                    Node::Leaf { start, count, .. } => {
+                        candidates.extend_from_slice(&self.shape_indices[*start..start + count]);
+                    }
+                    Node::Branch { left, right, .. } => {
+                        stack.push(*right);
+                        stack.push(*left);
+                    }
+                }
+            }
+        }
+        candidates.sort_unstable();
+
+        // both lists are sorted, merging them keeps order of shapes without copying unbounded ones
+        let mut unbounded = self.unbounded_shape_indices.iter().peekable();
+        for index in candidates {
+            while let Some(unbounded_index) = unbounded.next_if(|entry| **entry < index) {
+                ray.intersect(shapes[*unbounded_index].as_ref(), intersections);
+            }
+            ray.intersect(shapes[index].as_ref(), intersections);
+        }
+        for index in unbounded {
+            ray.intersect(shapes[*index].as_ref(), intersections);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composites::Material;
+    use crate::primitives::{Point, Vector, transformations};
+    use crate::shapes::{Plane, Sphere};
+
+    fn spheres_in_row(count: i32) -> Vec<Box<dyn Shape>> {
+        return (0..count)
+            .map(|index| {
+                let sphere = Sphere::new(
+                    Material::default(),
+                    transformations::translation(index * 3, 0, 0),
+                );
+                return Box::new(sphere) as Box<dyn Shape>;
+            })
+            .collect();
+    }
+
+    #[test]
+    fn unbounded_shapes_are_kept_aside() {
+        let mut shapes = spheres_in_row(2);
+        shapes.push(Box::new(Plane::default()));
+        let bvh = BoundingVolumeHierarchy::new(&shapes);
+        assert_eq!(bvh.unbounded_shape_indices, vec![2]);
+        assert_eq!(bvh.shape_indices.len(), 2);
+    }
+
+    #[test]
+    fn ray_only_tests_shapes_along_its_path() {
+        let shapes = spheres_in_row(20);
+        let bvh = BoundingVolumeHierarchy::new(&shapes);
+        let ray = Ray::new(Point::new(30, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        bvh.intersect(&ray, &shapes, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].shape, shapes[10].as_ref());
+    }
+
+    #[test]
+    fn ray_missing_every_shape() {
+        let shapes = spheres_in_row(20);
+        let bvh = BoundingVolumeHierarchy::new(&shapes);
+        let ray = Ray::new(Point::new(0, 5, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        bvh.intersect(&ray, &shapes, &mut intersections);
+        assert!(intersections.is_empty());
+    }
+}
@@ -1,7 +1,10 @@
-use crate::composites::{Canvas, Intersections, Ray, World};
-use crate::primitives::{Color, Point, Transformation};
+use crate::composites::{
+    Canvas, Integrator, Intersections, Motion, Projection, Ray, SamplingMode, World,
+};
+use crate::consts::PI;
+use crate::primitives::{Color, Transformation, Vector};
 use crate::shapes::Transform;
-use crate::utils::CoarseEq;
+use crate::utils::{CoarseEq, Random};
 use core::fmt::{Display, Formatter, Result};
 use indicatif::{ParallelProgressIterator, ProgressIterator, ProgressStyle};
 use rayon::prelude::*;
@@ -11,11 +14,24 @@ pub struct Camera {
     horizontal_size: u32,
     vertical_size: u32,
     field_of_view: f64,
+    projection: Projection,
     half_width: f64,
     half_height: f64,
     pixel_size: f64,
     transformation_inverse: Transformation,
-    origin: Point,
+    /// Number of rays averaged into each pixel
+    pub samples: u32,
+    pub sampling_mode: SamplingMode,
+    /// Radius of lens, 0 makes camera a pinhole with everything in focus
+    pub aperture: f64,
+    /// Distance from camera to plane which is in perfect focus
+    pub focal_distance: f64,
+    /// Time at which shutter opens, rays are cast at times between opening and closing of
+    /// shutter which blurs moving shapes, both times are clamped to range from 0 to
+    /// [Motion::END_TIME] covered by bounds of moving shapes
+    pub shutter_open: f64,
+    pub shutter_close: f64,
+    pub integrator: Integrator,
 }
 
 impl Camera {
@@ -23,8 +39,24 @@ impl Camera {
         "[{elapsed_precise}] {bar:50.white/gray}{percent}% {human_pos}/{human_len}";
 
     pub fn new(horizontal_size: u32, vertical_size: u32, field_of_view: impl Into<f64>) -> Self {
+        return Self::with_projection(
+            horizontal_size,
+            vertical_size,
+            field_of_view,
+            Projection::Perspective,
+        );
+    }
+
+    /// Creates camera with given [Projection], field of view is ignored by projections which
+    /// do not use it
+    pub fn with_projection(
+        horizontal_size: u32,
+        vertical_size: u32,
+        field_of_view: impl Into<f64>,
+        projection: Projection,
+    ) -> Self {
         let field_of_view = field_of_view.into();
-        let half_view = (field_of_view / 2.0).tan();
+        let half_view = projection.half_view(field_of_view);
         let aspect = f64::from(horizontal_size) / f64::from(vertical_size);
         let half_width: f64;
         let half_height: f64;
@@ -40,40 +72,137 @@ impl Camera {
             horizontal_size,
             vertical_size,
             field_of_view,
+            projection,
             half_width,
             half_height,
             pixel_size,
             transformation_inverse: Transformation::IDENTITY,
-            origin: Point::ORIGIN,
+            samples: 1,
+            sampling_mode: SamplingMode::Grid,
+            aperture: 0.0,
+            focal_distance: 1.0,
+            shutter_open: 0.0,
+            shutter_close: 0.0,
+            integrator: Integrator::Whitted,
         };
     }
 
     #[inline]
     pub fn ray_for_pixel(&self, pixel_x: u32, pixel_y: u32) -> Ray {
-        // the offset from the edge of the canvas to the pixel's center
-        let offset_x = (pixel_x as f64 + 0.5) * self.pixel_size;
-        let offset_y = (pixel_y as f64 + 0.5) * self.pixel_size;
+        return self.ray_for_pixel_offset(pixel_x, pixel_y, 0.5, 0.5);
+    }
+
+    /// Returns ray passing through pixel at given offset from its top left corner, offsets are
+    /// relative to pixel size
+    #[inline]
+    pub fn ray_for_pixel_offset(
+        &self,
+        pixel_x: u32,
+        pixel_y: u32,
+        pixel_offset_x: f64,
+        pixel_offset_y: f64,
+    ) -> Ray {
+        return self.ray_through_lens(pixel_x, pixel_y, pixel_offset_x, pixel_offset_y, 0.0, 0.0);
+    }
+
+    /// Returns ray starting at point of lens and passing through the point of focal plane seen
+    /// through pixel, lens coordinates in range [0, 1) are mapped onto disk of aperture radius
+    #[inline]
+    pub fn ray_through_lens(
+        &self,
+        pixel_x: u32,
+        pixel_y: u32,
+        pixel_offset_x: f64,
+        pixel_offset_y: f64,
+        lens_u: f64,
+        lens_v: f64,
+    ) -> Ray {
+        // the offset from the edge of the canvas to the sampled point of the pixel
+        let offset_x = (pixel_x as f64 + pixel_offset_x) * self.pixel_size;
+        let offset_y = (pixel_y as f64 + pixel_offset_y) * self.pixel_size;
 
         // the untransformed coordinates of the pixel in world space
         // (remember that the camera looks toward -z, so +x is to the *left*)
         let world_x = self.half_width - offset_x;
         let world_y = self.half_height - offset_y;
 
-        // using the camera matrix, transform the canvas point and the origin
-        // and then compute the ray's direction vector
-        // (remember that the canvas is at z = -1)
-        let pixel = self.transformation_inverse * Point::new(world_x, world_y, -1);
-        let direction = (pixel - self.origin).normalized();
-        return Ray::new(self.origin, direction);
+        // using the camera matrix, transform the ray given by the projection
+        // (remember that the perspective canvas is at z = -1)
+        let (origin, direction) = self.projection.camera_ray(world_x, world_y);
+        if self.aperture <= 0.0 || lens_u <= 0.0 {
+            let origin = self.transformation_inverse * origin;
+            let direction = (self.transformation_inverse * direction).normalized();
+            return Ray::new(origin, direction);
+        }
+
+        // the canvas point is pushed out to the focal plane, all rays through it from the lens
+        // meet there, so only objects at focal distance stay sharp
+        let focal_point = self.transformation_inverse * (origin + direction * self.focal_distance);
+        let lens_radius = self.aperture * lens_u.sqrt();
+        let lens_angle = 2.0 * PI * lens_v;
+        let lens_offset = Vector::new(
+            lens_radius * lens_angle.cos(),
+            lens_radius * lens_angle.sin(),
+            0,
+        );
+        let origin = self.transformation_inverse * (origin + lens_offset);
+        let direction = (focal_point - origin).normalized();
+        return Ray::new(origin, direction);
     }
 
     #[inline]
     fn render_pixel(&self, index: usize, pixel: &mut Color, world: &World) {
         let x: u32 = index as u32 % self.horizontal_size;
         let y: u32 = index as u32 / self.horizontal_size;
-        let ray = self.ray_for_pixel(x, y);
         let mut intersections = Intersections::new();
-        *pixel = world.color_at(&ray, &mut intersections);
+        *pixel = self.color_at_pixel(x, y, world, &mut intersections);
+    }
+
+    /// Returns average color of samples taken within pixel, samples are placed according to
+    /// [SamplingMode], with non zero aperture each of them passes through random point of lens
+    /// and with open shutter each is cast at different time, color of each sample is computed by
+    /// [Integrator] of camera, random offsets depend only on pixel coordinates
+    #[inline]
+    pub fn color_at_pixel<'shapes>(
+        &self,
+        pixel_x: u32,
+        pixel_y: u32,
+        world: &'shapes World,
+        intersections: &mut Intersections<'shapes>,
+    ) -> Color {
+        let sample_count = self.sampling_mode.sample_count(self.samples);
+        let mut random =
+            Random::new(u64::from(pixel_y) * u64::from(self.horizontal_size) + u64::from(pixel_x));
+        let mut color = Color::BLACK;
+        for index in 0..sample_count {
+            let (offset_x, offset_y) = self.sampling_mode.offset(index, self.samples, &mut random);
+            let mut ray = if self.aperture > 0.0 {
+                let (lens_u, lens_v) = (random.next_f64(), random.next_f64());
+                self.ray_through_lens(pixel_x, pixel_y, offset_x, offset_y, lens_u, lens_v)
+            } else {
+                self.ray_for_pixel_offset(pixel_x, pixel_y, offset_x, offset_y)
+            };
+            ray.time = self.sample_time(index, sample_count, &mut random);
+            color = color
+                + self
+                    .integrator
+                    .color_at(world, &ray, &mut random, intersections);
+        }
+        return color / f64::from(sample_count);
+    }
+
+    /// Returns time of sample with given index, times are stratified so that samples of each
+    /// pixel are spread over whole exposure
+    #[inline]
+    fn sample_time(&self, index: u32, sample_count: u32, random: &mut Random) -> f64 {
+        let shutter_open = self.shutter_open.clamp(0.0, Motion::END_TIME);
+        let shutter_close = self.shutter_close.clamp(0.0, Motion::END_TIME);
+        let exposure = shutter_close - shutter_open;
+        if exposure <= 0.0 {
+            return shutter_open;
+        }
+        let fraction = (f64::from(index) + random.next_f64()) / f64::from(sample_count);
+        return exposure.mul_add(fraction, shutter_open);
     }
 
     pub fn render(&self, world: &World) -> Canvas {
@@ -88,8 +217,7 @@ impl Camera {
             .enumerate()
             .for_each(|(index, pixel)| {
                 let (x, y) = Canvas::index_to_coords(canvas.width, index);
-                let ray = self.ray_for_pixel(x, y);
-                *pixel = world.color_at(&ray, &mut intersections);
+                *pixel = self.color_at_pixel(x, y, world, &mut intersections);
             });
         return canvas;
     }
@@ -105,14 +233,13 @@ impl Camera {
             .enumerate()
             .for_each_with(Intersections::new(), |intersections, (index, pixel)| {
                 let (x, y) = Canvas::index_to_coords(canvas.width, index);
-                let ray = self.ray_for_pixel(x, y);
-                *pixel = world.color_at(&ray, intersections);
+                *pixel = self.color_at_pixel(x, y, world, intersections);
             });
         return canvas;
     }
 
-    fn update_origin(&mut self) {
-        self.origin = self.transformation_inverse * Point::ORIGIN;
+    pub fn projection(&self) -> Projection {
+        return self.projection;
     }
 }
 
@@ -123,7 +250,6 @@ impl Transform for Camera {
 
     fn set_transformation(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation.inverse();
-        self.update_origin();
     }
 
     fn transformation_inverse(&self) -> Transformation {
@@ -132,7 +258,6 @@ impl Transform for Camera {
 
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
-        self.update_origin();
     }
 }
 
@@ -143,10 +268,18 @@ impl Display for Camera {
             .field("horizontal_size", &self.horizontal_size)
             .field("vertical_size", &self.vertical_size)
             .field("field_of_view", &self.field_of_view)
+            .field("projection", &self.projection)
             .field("half_width", &self.half_width)
             .field("half_height", &self.half_height)
             .field("pixel_size", &self.pixel_size)
             .field("transformation", &self.transformation())
+            .field("samples", &self.samples)
+            .field("sampling_mode", &self.sampling_mode)
+            .field("aperture", &self.aperture)
+            .field("focal_distance", &self.focal_distance)
+            .field("shutter_open", &self.shutter_open)
+            .field("shutter_close", &self.shutter_close)
+            .field("integrator", &self.integrator)
             .finish();
     }
 }
@@ -154,8 +287,10 @@ impl Display for Camera {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::consts::PI;
-    use crate::primitives::{Color, Vector, transformations};
+    use crate::composites::PathTracer;
+    use crate::primitives::{Color, Point, PointLight, transformations};
+    use crate::shapes::Sphere;
+    use rstest::rstest;
 
     #[test]
     fn constructing_camera() {
@@ -247,4 +382,231 @@ mod tests {
         );
         assert!(pixel.coarse_eq(&expected));
     }
+
+    #[test]
+    fn ray_through_pixel_offset() {
+        let camera = Camera::new(201, 101, PI / 2.0);
+        let ray = camera.ray_for_pixel_offset(100, 50, 0.5, 0.5);
+        assert_eq!(ray, camera.ray_for_pixel(100, 50));
+        let ray = camera.ray_for_pixel_offset(0, 0, 0.0, 0.0);
+        let expected = Point::new(camera.half_width, camera.half_height, -1) - Point::ORIGIN;
+        assert!(ray.direction.coarse_eq(&expected.normalized()));
+    }
+
+    #[test]
+    fn supersampling_averages_samples() {
+        let world = World::default();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(0, 0, -5),
+            Point::ORIGIN,
+            Vector::UP,
+        ));
+        camera.samples = 4;
+        let mut intersections = Intersections::new();
+        let color = camera.color_at_pixel(2, 2, &world, &mut intersections);
+        let expected = [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)]
+            .iter()
+            .map(|(x, y)| {
+                let ray = camera.ray_for_pixel_offset(2, 2, *x, *y);
+                return world.color_at(&ray, &mut intersections);
+            })
+            .fold(Color::BLACK, |sum, color| sum + color)
+            / 4.0;
+        assert_eq!(color, expected);
+    }
+
+    #[test]
+    fn camera_computes_samples_with_integrator() {
+        let world = World::default();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(0, 0, -5),
+            Point::ORIGIN,
+            Vector::UP,
+        ));
+        let mut intersections = Intersections::new();
+        let whitted_color = camera.color_at_pixel(5, 5, &world, &mut intersections);
+        camera.integrator = Integrator::PathTracing(PathTracer::new(1));
+        let path_traced_color = camera.color_at_pixel(5, 5, &world, &mut intersections);
+        assert_ne!(path_traced_color, whitted_color);
+        let repeated_color = camera.color_at_pixel(5, 5, &world, &mut intersections);
+        assert_eq!(repeated_color, path_traced_color);
+    }
+
+    #[rstest]
+    #[case(SamplingMode::Grid)]
+    #[case(SamplingMode::Jittered)]
+    #[case(SamplingMode::Stratified)]
+    fn serial_and_parallel_supersampling_match(#[case] sampling_mode: SamplingMode) {
+        let world = World::default();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(0, 0, -5),
+            Point::ORIGIN,
+            Vector::UP,
+        ));
+        camera.samples = 9;
+        camera.sampling_mode = sampling_mode;
+        assert_eq!(camera.render(&world), camera.render_parallel(&world));
+    }
+
+    #[test]
+    fn rays_through_lens_meet_at_focal_plane() {
+        let mut camera = Camera::new(201, 101, PI / 2.0);
+        camera.set_transformation(transformations::translation(0, 0, 5));
+        camera.aperture = 0.5;
+        camera.focal_distance = 4.0;
+        let focal_point = Point::new(0, 0, -9);
+        for (lens_u, lens_v) in [(0.3, 0.1), (0.9, 0.5), (0.6, 0.8)] {
+            let ray = camera.ray_through_lens(100, 50, 0.5, 0.5, lens_u, lens_v);
+            assert!(ray.origin.coarse_ne(&Point::new(0, 0, -5)));
+            assert!((ray.origin - Point::new(0, 0, -5)).magnitude() <= 0.5);
+            assert!(
+                ray.position(4.0 / ray.direction.dot(&Vector::BACKWARD))
+                    .coarse_eq(&focal_point)
+            );
+        }
+    }
+
+    #[test]
+    fn ray_through_lens_center_matches_pinhole() {
+        let mut camera = Camera::new(201, 101, PI / 2.0);
+        camera.aperture = 0.5;
+        camera.focal_distance = 3.0;
+        let ray = camera.ray_through_lens(0, 0, 0.5, 0.5, 0.0, 0.7);
+        assert_eq!(ray, camera.ray_for_pixel(0, 0));
+    }
+
+    #[test]
+    fn closed_shutter_samples_single_time() {
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.shutter_open = 0.3;
+        camera.shutter_close = 0.3;
+        let mut random = Random::new(0);
+        assert_eq!(camera.sample_time(0, 4, &mut random), 0.3);
+    }
+
+    #[test]
+    fn sample_times_are_spread_over_exposure() {
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.shutter_open = 0.5;
+        camera.shutter_close = 1.0;
+        let mut random = Random::new(0);
+        for index in 0..4 {
+            let time = camera.sample_time(index, 4, &mut random);
+            let start = 0.5 + f64::from(index) * 0.125;
+            assert!((start..start + 0.125).contains(&time));
+        }
+    }
+
+    #[test]
+    fn shutter_times_are_clamped_to_motion_range() {
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.shutter_open = -0.5;
+        camera.shutter_close = 1.5;
+        let mut random = Random::new(0);
+        assert!((0.0..0.25).contains(&camera.sample_time(0, 4, &mut random)));
+        assert!((0.75..Motion::END_TIME).contains(&camera.sample_time(3, 4, &mut random)));
+        camera.shutter_open = 1.5;
+        assert_eq!(camera.sample_time(0, 4, &mut random), Motion::END_TIME);
+    }
+
+    #[test]
+    fn moving_sphere_with_bvh_matches_brute_force_at_shutter_close() {
+        let create_world = || {
+            let mut sphere = Sphere::default();
+            sphere.motion = Motion::new(Vector::new(4, 0, 0), Vector::ZERO, Point::ORIGIN);
+            return World::new(
+                vec![Box::new(PointLight::new(
+                    Point::new(0, 0, -10),
+                    Color::WHITE,
+                ))],
+                vec![Box::new(sphere)],
+            );
+        };
+        let mut world = create_world();
+        world.use_bvh = false;
+        let world_with_bvh = create_world();
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(4, 0, -5),
+            Point::new(4, 0, 0),
+            Vector::UP,
+        ));
+        for shutter_close in [Motion::END_TIME, 1.5] {
+            camera.shutter_open = shutter_close;
+            camera.shutter_close = shutter_close;
+            let canvas = camera.render(&world);
+            assert_eq!(canvas, camera.render(&world_with_bvh));
+            assert!(canvas.get_pixel(5, 5).coarse_ne(&Color::BLACK));
+        }
+    }
+
+    #[test]
+    fn moving_sphere_is_blurred() {
+        let mut sphere = Sphere::default();
+        sphere.motion = Motion::new(Vector::new(2, 0, 0), Vector::ZERO, Point::ORIGIN);
+        let world = World::new(
+            vec![Box::new(PointLight::new(
+                Point::new(0, 0, -10),
+                Color::WHITE,
+            ))],
+            vec![Box::new(sphere)],
+        );
+        let mut camera = Camera::new(11, 11, PI / 2.0);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(0, 0, -5),
+            Point::ORIGIN,
+            Vector::UP,
+        ));
+        camera.samples = 16;
+        let mut intersections = Intersections::new();
+        let color_at_rest = camera.color_at_pixel(5, 5, &world, &mut intersections);
+        camera.shutter_close = 1.0;
+        let blurred_color = camera.color_at_pixel(5, 5, &world, &mut intersections);
+        assert!(blurred_color.red > 0.0);
+        assert!(blurred_color.red < color_at_rest.red);
+    }
+
+    #[test]
+    fn orthographic_rays_share_direction() {
+        let camera =
+            Camera::with_projection(201, 101, PI / 2.0, Projection::Orthographic { width: 4.0 });
+        assert!(camera.pixel_size.coarse_eq(&(4.0 / 201.0)));
+        let center = camera.ray_for_pixel(100, 50);
+        assert!(center.origin.coarse_eq(&Point::ORIGIN));
+        let corner = camera.ray_for_pixel_offset(0, 0, 0.0, 0.0);
+        let expected = Point::new(camera.half_width, camera.half_height, 0);
+        assert!(corner.origin.coarse_eq(&expected));
+        assert_eq!(corner.direction, center.direction);
+    }
+
+    #[test]
+    fn equirectangular_camera_sees_behind_itself() {
+        let camera = Camera::with_projection(200, 100, 0, Projection::Equirectangular);
+        let ray = camera.ray_for_pixel_offset(0, 50, 0.0, 0.0);
+        assert!(ray.direction.coarse_eq(&Vector::FORWARD));
+        let ray = camera.ray_for_pixel_offset(100, 50, 0.0, 0.0);
+        assert!(ray.direction.coarse_eq(&Vector::BACKWARD));
+        let ray = camera.ray_for_pixel_offset(50, 0, 0.0, 0.0);
+        assert!(ray.direction.coarse_eq(&Vector::UP));
+    }
+
+    #[rstest]
+    #[case(Projection::Orthographic { width: 3.0 })]
+    #[case(Projection::Fisheye)]
+    #[case(Projection::Equirectangular)]
+    fn serial_and_parallel_projections_match(#[case] projection: Projection) {
+        let world = World::default();
+        let mut camera = Camera::with_projection(22, 11, PI, projection);
+        camera.set_transformation(transformations::view_transform(
+            Point::new(0, 0, -5),
+            Point::ORIGIN,
+            Vector::UP,
+        ));
+        let canvas = camera.render(&world);
+        assert_eq!(canvas, camera.render_parallel(&world));
+        assert!(canvas.get_pixel(11, 5).coarse_ne(&Color::BLACK));
+    }
 }
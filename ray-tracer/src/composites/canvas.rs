@@ -135,6 +135,26 @@ impl Canvas {
             image::ExtendedColorType::Rgb8,
         )?);
     }
+
+    /// Loads image in any format supported by `image` crate, channels are scaled to range [0, 1]
+    /// without gamma correction, which mirrors [Canvas::to_png_file]
+    pub fn from_image_file<P: AsRef<Path>>(file_name: P) -> Result<Self, Box<dyn Error>> {
+        let image = image::open(file_name)?.into_rgb8();
+        let pixels = image
+            .pixels()
+            .map(|pixel| {
+                let [red, green, blue] = pixel.0.map(|channel| {
+                    return f64::from(channel) / Self::MAX_COLOR_VALUE;
+                });
+                return Color::new(red, green, blue);
+            })
+            .collect();
+        return Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            pixels,
+        });
+    }
 }
 
 impl Deref for Canvas {
@@ -148,6 +168,7 @@ impl Deref for Canvas {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn new_canvas() {
@@ -200,4 +221,37 @@ mod tests {
             "  0   0   0   0   0   0   0   0   0   0   0   0   0   0 255"
         );
     }
+
+    /// Path in temporary directory named after process and test, so that concurrent test runs do
+    /// not share it, file is removed when test ends even if it fails
+    struct TemporaryFile(PathBuf);
+
+    impl TemporaryFile {
+        fn new(test_name: &str) -> Self {
+            let name = format!("ray_tracer_{}_{test_name}.png", std::process::id());
+            return Self(std::env::temp_dir().join(name));
+        }
+    }
+
+    impl Drop for TemporaryFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn png_file_round_trip() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.set_pixel(0, 0, Color::RED);
+        canvas.set_pixel(2, 1, Color::new(0, 0.2, 1));
+        let file = TemporaryFile::new("png_file_round_trip");
+        canvas.to_png_file(&file.0).unwrap();
+        let loaded = Canvas::from_image_file(&file.0).unwrap();
+        assert_eq!(loaded, canvas);
+    }
+
+    #[test]
+    fn loading_missing_image_file() {
+        assert!(Canvas::from_image_file("missing/image.png").is_err());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::consts::EPSILON;
-use crate::primitives::{Point, Vector};
+use crate::primitives::{Color, Point, Vector};
 use crate::shapes::Shape;
 use crate::utils::Squared;
 
@@ -16,6 +16,10 @@ pub struct ComputedHit<'shape> {
     pub refractive_index_1: f64,
     pub refractive_index_2: f64,
     pub is_inside: bool,
+    /// Time of ray which produced hit, rays cast from hit use the same time
+    pub time: f64,
+    /// Fraction of light which is not absorbed by medium on its way from hit to origin of ray
+    pub transmittance: Color,
 }
 
 impl ComputedHit<'_> {
@@ -29,6 +33,7 @@ impl ComputedHit<'_> {
         refractive_index_1: f64,
         refractive_index_2: f64,
         is_inside: bool,
+        time: f64,
     ) -> ComputedHit {
         let over_point = point + (normal * EPSILON);
         let under_point = point - (normal * EPSILON);
@@ -44,9 +49,28 @@ impl ComputedHit<'_> {
             refractive_index_1,
             refractive_index_2,
             is_inside,
+            time,
+            transmittance: Color::WHITE,
         };
     }
 
+    /// Returns direction of ray refracted at hit, [None] under total internal reflection
+    pub fn refract_direction(&self) -> Option<Vector> {
+        let n_ratio = self.refractive_index_1 / self.refractive_index_2;
+        let cos_i = self.camera_direction.dot(&self.normal);
+        let sin2_t = n_ratio.squared() * (1.0 - cos_i.squared());
+        let is_total_internal_reflection = sin2_t > 1.0;
+
+        if is_total_internal_reflection {
+            return None;
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        return Some(
+            self.normal * n_ratio.mul_add(cos_i, -cos_t) - (self.camera_direction * n_ratio),
+        );
+    }
+
     pub fn schlicks_approximation(&self) -> f64 {
         let mut cos = self.camera_direction.dot(&self.normal);
 
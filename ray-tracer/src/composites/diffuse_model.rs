@@ -0,0 +1,89 @@
+use crate::primitives::Vector;
+use crate::utils::Squared;
+use core::fmt::{Display, Formatter, Result};
+
+/// Model of light scattered by rough surface below its microfacets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DiffuseModel {
+    /// Light is scattered evenly in all directions
+    #[default]
+    Lambert,
+    /// Rough surface scatters more light back towards light source, which makes materials such
+    /// as clay or plaster look flatter than with [DiffuseModel::Lambert]
+    OrenNayar,
+}
+
+impl DiffuseModel {
+    /// Returns factor by which Lambertian reflection is scaled for surface with given roughness
+    pub fn factor(
+        &self,
+        roughness: f64,
+        light_direction: &Vector,
+        camera_direction: &Vector,
+        normal: &Vector,
+    ) -> f64 {
+        return match self {
+            Self::Lambert => 1.0,
+            Self::OrenNayar => {
+                let sigma2 = roughness.squared();
+                let a = 1.0 - 0.5 * sigma2 / (sigma2 + 0.33);
+                let b = 0.45 * sigma2 / (sigma2 + 0.09);
+                let light_dot_normal = light_direction.dot(normal).clamp(-1.0, 1.0);
+                let camera_dot_normal = camera_direction.dot(normal).clamp(-1.0, 1.0);
+                let light_tangent = *light_direction - *normal * light_dot_normal;
+                let camera_tangent = *camera_direction - *normal * camera_dot_normal;
+                let tangent_magnitudes = light_tangent.magnitude() * camera_tangent.magnitude();
+                if tangent_magnitudes == 0.0 {
+                    return a;
+                }
+                let cos_azimuth = light_tangent.dot(&camera_tangent) / tangent_magnitudes;
+                let light_angle = light_dot_normal.acos();
+                let camera_angle = camera_dot_normal.acos();
+                let alpha = light_angle.max(camera_angle);
+                let beta = light_angle.min(camera_angle);
+                b.mul_add(cos_azimuth.max(0.0) * alpha.sin() * beta.tan(), a)
+            }
+        };
+    }
+}
+
+impl Display for DiffuseModel {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let name = match self {
+            Self::Lambert => "lambert",
+            Self::OrenNayar => "oren-nayar",
+        };
+        return formatter.write_str(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+
+    #[test]
+    fn lambert_does_not_scale_reflection() {
+        let light = Vector::new(1, 1, 0).normalized();
+        let factor = DiffuseModel::Lambert.factor(1.0, &light, &Vector::UP, &Vector::UP);
+        assert_eq!(factor, 1.0);
+    }
+
+    #[test]
+    fn smooth_oren_nayar_matches_lambert() {
+        let light = Vector::new(1, 1, 0).normalized();
+        let camera = Vector::new(-1, 2, 0).normalized();
+        let factor = DiffuseModel::OrenNayar.factor(0.0, &light, &camera, &Vector::UP);
+        assert!(factor.coarse_eq(&1.0));
+    }
+
+    #[test]
+    fn rough_oren_nayar_favors_back_scattering() {
+        let light = Vector::new(1, 1, 0).normalized();
+        let back = DiffuseModel::OrenNayar.factor(1.0, &light, &light, &Vector::UP);
+        let camera = Vector::new(-1, 1, 0).normalized();
+        let forward = DiffuseModel::OrenNayar.factor(1.0, &light, &camera, &Vector::UP);
+        assert!(back > forward);
+        assert!(forward < 1.0);
+    }
+}
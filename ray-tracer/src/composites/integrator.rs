@@ -0,0 +1,60 @@
+use crate::composites::{Intersections, PathTracer, Ray, World};
+use crate::primitives::Color;
+use crate::utils::Random;
+use core::fmt::{Display, Formatter, Result};
+
+/// Method of computing color of light arriving along camera ray
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Integrator {
+    /// Recursive ray tracing which follows only mirror reflections and refractions, light
+    /// bounced between diffuse surfaces is approximated by ambient term of materials
+    #[default]
+    Whitted,
+    /// Monte Carlo path tracing which follows random bounces and captures indirect lighting
+    PathTracing(PathTracer),
+}
+
+impl Integrator {
+    /// Returns color seen along [Ray], random generator is used only by stochastic integrators
+    #[inline]
+    pub fn color_at<'shapes>(
+        &self,
+        world: &'shapes World,
+        ray: &Ray,
+        random: &mut Random,
+        intersections: &mut Intersections<'shapes>,
+    ) -> Color {
+        return match self {
+            Self::Whitted => world.color_at(ray, intersections),
+            Self::PathTracing(path_tracer) => {
+                path_tracer.color_at(world, ray, random, intersections)
+            }
+        };
+    }
+}
+
+impl Display for Integrator {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return match self {
+            Self::Whitted => formatter.write_str("whitted"),
+            Self::PathTracing(path_tracer) => write!(formatter, "{path_tracer}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Point, Vector};
+
+    #[test]
+    fn whitted_integrator_matches_world() {
+        let world = World::default();
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        let expected = world.color_at(&ray, &mut intersections);
+        let color =
+            Integrator::Whitted.color_at(&world, &ray, &mut Random::new(0), &mut intersections);
+        assert_eq!(color, expected);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::composites::{ComputedHit, Intersections, Material, Ray};
+use crate::primitives::Color;
 use crate::shapes::Shape;
 use crate::utils::CoarseEq;
 use core::cmp::Ordering;
@@ -8,19 +9,34 @@ use core::fmt::Debug;
 pub struct Intersection<'shape> {
     pub distance: f64,
     pub shape: &'shape dyn Shape,
+    pub u: f64,
+    pub v: f64,
 }
 
 impl Intersection<'_> {
     pub fn new(distance: impl Into<f64>, shape: &dyn Shape) -> Intersection {
+        return Intersection::with_uv(distance, shape, 0, 0);
+    }
+
+    /// Creates [Intersection] which also records where on the surface of shape it happened,
+    /// triangles use barycentric coordinates of the hit
+    pub fn with_uv(
+        distance: impl Into<f64>,
+        shape: &dyn Shape,
+        u: impl Into<f64>,
+        v: impl Into<f64>,
+    ) -> Intersection<'_> {
         return Intersection {
             distance: distance.into(),
             shape,
+            u: u.into(),
+            v: v.into(),
         };
     }
 
     pub fn prepare_computations(&self, ray: &Ray, intersections: &Intersections) -> ComputedHit {
         let point = ray.position(self.distance);
-        let mut normal = self.shape.normal_at(point);
+        let mut normal = self.shape.normal_at_uv(point, self.u, self.v, ray.time);
         let camera_direction = -ray.direction;
         let is_inside = normal.dot(&camera_direction) < 0.0;
 
@@ -33,6 +49,7 @@ impl Intersection<'_> {
         let mut shapes: Vec<&dyn Shape> = Vec::new();
         let mut refractive_index_1: f64 = Material::DEFAULT_REFRACTIVE_INDEX;
         let mut refractive_index_2: f64 = Material::DEFAULT_REFRACTIVE_INDEX;
+        let mut transmittance = Color::WHITE;
 
         for intersection in intersections {
             let is_self = self == intersection;
@@ -42,6 +59,10 @@ impl Intersection<'_> {
                     .map_or(Material::DEFAULT_REFRACTIVE_INDEX, |shape| {
                         shape.material().refractive_index
                     });
+                // ray reached hit through medium of the innermost shape containing it
+                transmittance = shapes.last().map_or(Color::WHITE, |shape| {
+                    shape.material().transmittance(self.distance)
+                });
             }
 
             let position = shapes.iter().position(|shape| *shape == intersection.shape);
@@ -61,7 +82,7 @@ impl Intersection<'_> {
             }
         }
 
-        return ComputedHit::new(
+        let mut computed_hit = ComputedHit::new(
             self.distance,
             self.shape,
             point,
@@ -71,7 +92,22 @@ impl Intersection<'_> {
             refractive_index_1,
             refractive_index_2,
             is_inside,
+            ray.time,
         );
+        computed_hit.transmittance = transmittance;
+
+        // over and under points stay offset along geometric normal, so that perturbed normal
+        // can't push them below surface, outward normal is perturbed so that tangent frame of
+        // perturbation is not mirrored inside of shape
+        if let Some(perturbation) = &self.shape.material().normal_perturbation {
+            let outward_normal = if is_inside { -normal } else { normal };
+            let perturbed =
+                perturbation.perturb_at_shape(self.shape, &point, &outward_normal, ray.time);
+            let normal = if is_inside { -perturbed } else { perturbed };
+            computed_hit.normal = normal;
+            computed_hit.reflect_direction = ray.direction.reflect(&normal);
+        }
+        return computed_hit;
     }
 
     pub fn is_within_distance(&self, distance: f64) -> bool {
@@ -103,11 +139,16 @@ impl Ord for Intersection<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::composites::{Intersections, Material, Ray};
-    use crate::consts::EPSILON;
+    use crate::composites::{Canvas, Intersections, Material, Ray};
+    use crate::consts::{EPSILON, PI};
+    use crate::patterns::{
+        NoisePerturbation, NormalMapPerturbation, PerlinNoise, UvImagePattern, UvMapping,
+    };
     use crate::primitives::transformations;
-    use crate::primitives::{Point, Vector};
+    use crate::primitives::{Color, Point, Vector};
     use crate::shapes::{Plane, Shape, Sphere, Transform};
+    use crate::utils::CoarseEq;
+    use std::sync::Arc;
 
     #[test]
     fn new_intersection() {
@@ -252,4 +293,67 @@ mod tests {
         assert!(computed_hit.under_point.z > EPSILON / 2.0);
         assert!(computed_hit.point.z < computed_hit.under_point.z);
     }
+
+    #[test]
+    fn light_is_absorbed_by_medium_between_hit_and_ray_origin() {
+        let mut sphere = Sphere::default();
+        sphere.material = Material::glass();
+        sphere.material.absorption_color = Color::new(0.5, 1, 0.25);
+        sphere.material.absorption_density = 0.5;
+        let ray = Ray::new(Point::ORIGIN, Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        intersections.push(Intersection::new(-1, &sphere));
+        intersections.push(Intersection::new(1, &sphere));
+        let computed_hit = intersections[1].prepare_computations(&ray, &intersections);
+        assert!(
+            computed_hit
+                .transmittance
+                .coarse_eq(&Color::new(0.5_f64.sqrt(), 1, 0.5))
+        );
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        intersections.push(Intersection::new(4, &sphere));
+        intersections.push(Intersection::new(6, &sphere));
+        let computed_hit = intersections[0].prepare_computations(&ray, &intersections);
+        assert_eq!(computed_hit.transmittance, Color::WHITE);
+    }
+
+    #[test]
+    fn normal_map_turns_together_with_rotated_sphere() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.set_pixel(0, 0, Color::new(1, 0.5, 0.5));
+        let normal_map =
+            NormalMapPerturbation::new(Arc::new(UvImagePattern::new(canvas)), UvMapping::Spherical);
+        let mut sphere = Sphere::new(Material::default(), transformations::rotation_z(PI / 2.0));
+        sphere.material.normal_perturbation = Some(Arc::new(normal_map));
+        let boxed_shape: Box<dyn Shape> = Box::new(sphere);
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let intersection = Intersection::new(4, boxed_shape.as_ref());
+        let computed_hit = intersection.prepare_computations(&ray, &Intersections::new());
+        assert!(computed_hit.normal.coarse_eq(&Vector::DOWN));
+        let ray = Ray::new(Point::ORIGIN, Vector::BACKWARD);
+        let intersection = Intersection::new(1, boxed_shape.as_ref());
+        let computed_hit = intersection.prepare_computations(&ray, &Intersections::new());
+        assert!(computed_hit.is_inside);
+        assert!(computed_hit.normal.coarse_eq(&Vector::UP));
+    }
+
+    #[test]
+    fn normal_perturbation_changes_shading_normal_only() {
+        let mut plane = Plane::default();
+        plane.material.normal_perturbation = Some(Arc::new(NoisePerturbation::new(
+            PerlinNoise::default(),
+            0.5,
+        )));
+        let boxed_shape: Box<dyn Shape> = Box::new(plane);
+        let ray = Ray::new(Point::new(0.3, 1, -0.6), Vector::DOWN);
+        let intersection = Intersection::new(1, boxed_shape.as_ref());
+        let computed_hit = intersection.prepare_computations(&ray, &Intersections::new());
+        assert!(computed_hit.normal.coarse_ne(&Vector::UP));
+        assert_eq!(computed_hit.over_point, Point::new(0.3, EPSILON, -0.6));
+        assert_eq!(
+            computed_hit.reflect_direction,
+            Vector::DOWN.reflect(&computed_hit.normal)
+        );
+    }
 }
@@ -1,7 +1,9 @@
-use crate::composites::ComputedHit;
-use crate::patterns::Pattern;
+use crate::composites::{ComputedHit, ShadingModel};
+use crate::consts::PI;
+use crate::patterns::{NormalPerturbation, Pattern};
 use crate::primitives::{Color, Light, Point, Vector};
 use crate::shapes::Shape;
+use crate::utils::Random;
 use core::fmt::{Display, Formatter, Result};
 use std::sync::Arc;
 
@@ -9,6 +11,11 @@ use std::sync::Arc;
 pub struct Material {
     pub color: Color,
     pub pattern: Option<Arc<dyn Pattern>>,
+    /// Changes normal used for shading, see [NormalPerturbation]
+    pub normal_perturbation: Option<Arc<dyn NormalPerturbation>>,
+    /// Light given off by surface regardless of lighting, with path tracing emissive shapes
+    /// light their surroundings
+    pub emissive: Color,
     pub ambient: f64,
     pub diffuse: f64,
     pub specular: f64,
@@ -16,13 +23,27 @@ pub struct Material {
     pub reflectiveness: f64,
     pub refractive_index: f64,
     pub transparency: f64,
+    /// Color of white light after it travels unit distance through material at unit density
+    pub absorption_color: Color,
+    /// Density of absorbing medium inside transparent material, 0 absorbs no light
+    pub absorption_density: f64,
     pub casts_shadow: bool,
+    /// Spread of reflected and refracted rays, 0 gives perfect mirror and clear glass while
+    /// higher values blur reflections like brushed metal and refractions like frosted glass
+    pub roughness: f64,
+    /// Number of rays averaged into rough reflection and refraction seen directly by camera,
+    /// deeper bounces use a single ray
+    pub glossy_samples: u32,
+    /// Phong by default, see [ShadingModel]
+    pub shading_model: ShadingModel,
 }
 
 impl Material {
     /// Refractive index of air
     pub const DEFAULT_REFRACTIVE_INDEX: f64 = 1.0;
 
+    pub const DEFAULT_GLOSSY_SAMPLES: u32 = 8;
+
     pub fn new(
         color: Color,
         pattern: Option<Arc<dyn Pattern>>,
@@ -38,6 +59,8 @@ impl Material {
         return Self {
             color,
             pattern,
+            normal_perturbation: None,
+            emissive: Color::BLACK,
             ambient: ambient.into(),
             diffuse: diffuse.into(),
             specular: specular.into(),
@@ -45,88 +68,222 @@ impl Material {
             reflectiveness: reflectiveness.into(),
             transparency: transparency.into(),
             refractive_index: refractive_index.into(),
+            absorption_color: Color::WHITE,
+            absorption_density: 0.0,
             casts_shadow,
+            roughness: 0.0,
+            glossy_samples: Self::DEFAULT_GLOSSY_SAMPLES,
+            shading_model: ShadingModel::Phong,
         };
     }
 
+    /// Returns color of surface lit by [Light], light transmission is fraction of light which
+    /// reaches the point averaged over light samples, black when it is fully in shadow and white
+    /// when fully lit, transparent shapes in the way tint it, moving shapes are shaded at their
+    /// original position
     #[inline]
     pub fn lighting(
         &self,
         shape: &dyn Shape,
-        light: &Light,
+        light: &dyn Light,
         point: &Point,
         camera_direction: &Vector,
         normal: &Vector,
-        in_shadow: bool,
+        light_transmission: Color,
     ) -> Color {
-        let effective_color = self.resolve_color(shape, point) * light.intensity;
+        let surface_color = self.resolve_color(shape, point, 0.0);
 
         return self.calculate_lighting(
-            &effective_color,
+            &surface_color,
             light,
             point,
             camera_direction,
             normal,
-            in_shadow,
+            light_transmission,
         );
     }
 
     #[inline]
-    fn resolve_color(&self, shape: &dyn Shape, point: &Point) -> Color {
-        return self
-            .pattern
-            .as_ref()
-            .map_or(self.color, |pattern| pattern.color_at_shape(shape, point));
+    fn resolve_color(&self, shape: &dyn Shape, point: &Point, time: f64) -> Color {
+        return self.pattern.as_ref().map_or(self.color, |pattern| {
+            pattern.color_at_shape(shape, point, time)
+        });
     }
 
     #[inline]
     fn calculate_lighting(
         &self,
-        effective_color: &Color,
-        light: &Light,
+        surface_color: &Color,
+        light: &dyn Light,
         point: &Point,
         camera_direction: &Vector,
         normal: &Vector,
-        in_shadow: bool,
+        light_transmission: Color,
     ) -> Color {
-        let ambient = *effective_color * self.ambient;
+        let ambient = *surface_color * light.ambient_intensity(point) * self.ambient;
+        let (diffuse, specular) = self.calculate_direct_lighting(
+            surface_color,
+            light,
+            point,
+            camera_direction,
+            normal,
+            light_transmission,
+        );
+        return ambient + diffuse + specular;
+    }
 
-        if in_shadow {
-            return ambient;
+    /// Returns diffuse and specular contributions of [Light], which together with ambient term
+    /// make up lighting of surface
+    #[inline]
+    fn calculate_direct_lighting(
+        &self,
+        surface_color: &Color,
+        light: &dyn Light,
+        point: &Point,
+        camera_direction: &Vector,
+        normal: &Vector,
+        light_transmission: Color,
+    ) -> (Color, Color) {
+        if light_transmission
+            .channels()
+            .iter()
+            .all(|value| *value <= 0.0)
+        {
+            return (Color::BLACK, Color::BLACK);
         }
 
-        let light_direction = (light.position - *point).normalized();
-        let light_dot_normal = light_direction.dot(normal);
-        if light_dot_normal < 0.0 {
-            return ambient;
-        }
-        let diffuse = *effective_color * self.diffuse * light_dot_normal;
-        let reflect_direction = (-light_direction).reflect(normal);
-        let reflect_dot_camera = reflect_direction.dot(camera_direction);
+        let sample_count = light.sample_count();
+        let mut diffuse = Color::BLACK;
+        let mut specular = Color::BLACK;
+        for index in 0..sample_count {
+            let sample = light.sample(point, index);
+            let light_dot_normal = sample.direction.dot(normal);
+            if light_dot_normal < 0.0 {
+                continue;
+            }
+            match &self.shading_model {
+                ShadingModel::Phong => {
+                    let effective_color = *surface_color * sample.intensity;
+                    diffuse = diffuse + effective_color * self.diffuse * light_dot_normal;
+                    let reflect_direction = (-sample.direction).reflect(normal);
+                    let reflect_dot_camera = reflect_direction.dot(camera_direction);
 
-        if reflect_dot_camera <= 0.0 {
-            return ambient + diffuse;
+                    if reflect_dot_camera > 0.0 {
+                        let factor = reflect_dot_camera.powf(self.shininess);
+                        specular = specular + sample.intensity * self.specular * factor;
+                    }
+                }
+                ShadingModel::Microfacet(microfacet) => {
+                    let (sample_diffuse, sample_specular) = microfacet.reflectance(
+                        surface_color,
+                        &sample.direction,
+                        camera_direction,
+                        normal,
+                    );
+                    diffuse = diffuse + sample_diffuse * sample.intensity;
+                    specular = specular + sample_specular * sample.intensity;
+                }
+            }
         }
-        let factor = reflect_dot_camera.powf(self.shininess);
-        let specular = light.intensity * self.specular * factor;
 
-        return ambient + diffuse + specular;
+        let scale = light_transmission / sample_count as f64;
+        return (diffuse * scale, specular * scale);
     }
 
     pub fn lighting_from_computed_hit(
         &self,
         computed_hit: &ComputedHit,
-        light: &Light,
-        in_shadow: bool,
+        light: &dyn Light,
+        light_transmission: Color,
     ) -> Color {
-        return self.lighting(
+        return self.calculate_lighting(
+            &self.color_at_computed_hit(computed_hit),
+            light,
+            &computed_hit.over_point,
+            &computed_hit.camera_direction,
+            &computed_hit.normal,
+            light_transmission,
+        );
+    }
+
+    /// Returns color of surface at hit, taken from pattern when [Material] has one
+    pub fn color_at_computed_hit(&self, computed_hit: &ComputedHit) -> Color {
+        return self.resolve_color(
             computed_hit.shape,
+            &computed_hit.over_point,
+            computed_hit.time,
+        );
+    }
+
+    /// Returns light arriving directly from [Light] and scattered towards camera, unlike
+    /// [Material::lighting_from_computed_hit] it leaves out ambient term
+    pub fn direct_lighting_from_computed_hit(
+        &self,
+        computed_hit: &ComputedHit,
+        light: &dyn Light,
+        light_transmission: Color,
+    ) -> Color {
+        let (diffuse, specular) = self.calculate_direct_lighting(
+            &self.color_at_computed_hit(computed_hit),
             light,
             &computed_hit.over_point,
             &computed_hit.camera_direction,
             &computed_hit.normal,
-            in_shadow,
+            light_transmission,
         );
+        return diffuse + specular;
+    }
+
+    /// Returns fraction of light which passes through shape made of [Material] on its way to
+    /// shadowed point, opaque shapes block all light and transparent ones filter it through
+    /// their color, light absorbed inside of shape is given separately by
+    /// [Material::transmittance]
+    pub fn shadow_transmission(&self, shape: &dyn Shape, point: &Point, time: f64) -> Color {
+        if !self.casts_shadow {
+            return Color::WHITE;
+        }
+        if self.is_opaque() {
+            return Color::BLACK;
+        }
+        return self.resolve_color(shape, point, time) * self.transparency;
+    }
+
+    /// Returns fraction of light which passes given distance through material, it decreases
+    /// exponentially with distance according to Beer–Lambert law
+    pub fn transmittance(&self, distance: f64) -> Color {
+        if self.absorption_density <= 0.0 {
+            return Color::WHITE;
+        }
+        let exponent = self.absorption_density * distance.max(0.0);
+        return self.absorption_color.map(|value| value.powf(exponent));
+    }
+
+    /// Returns direction randomly spread around direction of perfect reflection or refraction
+    /// according to roughness, spread direction stays on the same side of surface
+    pub fn glossy_direction(
+        &self,
+        direction: &Vector,
+        normal: &Vector,
+        random: &mut Random,
+    ) -> Vector {
+        if self.is_smooth() {
+            return *direction;
+        }
+        let (tangent, bitangent) = direction.orthonormal_basis();
+        let radius = self.roughness * random.next_f64().sqrt();
+        let angle = 2.0 * PI * random.next_f64();
+        let spread =
+            (*direction + tangent * (radius * angle.cos()) + bitangent * (radius * angle.sin()))
+                .normalized();
+        let spread_dot_normal = spread.dot(normal);
+        if spread_dot_normal * direction.dot(normal) < 0.0 {
+            return spread - *normal * (2.0 * spread_dot_normal);
+        }
+        return spread;
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        return self.roughness == 0.0;
     }
 
     pub fn is_unreflective(&self) -> bool {
@@ -166,6 +323,8 @@ impl Display for Material {
             .debug_struct("Material")
             .field("color", &self.color)
             .field("pattern", &self.pattern)
+            .field("normal_perturbation", &self.normal_perturbation)
+            .field("emissive", &self.emissive)
             .field("ambient", &self.ambient)
             .field("diffuse", &self.diffuse)
             .field("specular", &self.specular)
@@ -173,6 +332,11 @@ impl Display for Material {
             .field("reflectiveness", &self.reflectiveness)
             .field("refractive_index", &self.refractive_index)
             .field("transparency", &self.transparency)
+            .field("absorption_color", &self.absorption_color)
+            .field("absorption_density", &self.absorption_density)
+            .field("roughness", &self.roughness)
+            .field("glossy_samples", &self.glossy_samples)
+            .field("shading_model", &self.shading_model)
             .finish();
     }
 }
@@ -180,14 +344,18 @@ impl Display for Material {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::Light;
+    use crate::composites::{Intersection, Intersections, Microfacet, Ray};
+    use crate::primitives::{AreaLight, Attenuation, PointLight};
     use crate::shapes::Sphere;
+    use crate::utils::CoarseEq;
     use core::default::Default;
+    use rstest::rstest;
 
     #[test]
     fn default_material() {
         let material = Material::default();
         assert_eq!(material.color, Color::WHITE);
+        assert_eq!(material.emissive, Color::BLACK);
         assert_eq!(material.ambient, 0.1);
         assert_eq!(material.diffuse, 0.9);
         assert_eq!(material.specular, 0.9);
@@ -195,6 +363,74 @@ mod tests {
         assert_eq!(material.reflectiveness, 0.0);
         assert_eq!(material.transparency, 0.0);
         assert_eq!(material.refractive_index, 1.0);
+        assert_eq!(material.roughness, 0.0);
+        assert_eq!(material.glossy_samples, Material::DEFAULT_GLOSSY_SAMPLES);
+        assert_eq!(material.shading_model, ShadingModel::Phong);
+    }
+
+    #[test]
+    fn transmittance_decreases_with_distance() {
+        let mut material = Material::glass();
+        assert_eq!(material.transmittance(10.0), Color::WHITE);
+        material.absorption_color = Color::new(0.5, 1, 0.25);
+        material.absorption_density = 2.0;
+        assert_eq!(material.transmittance(0.0), Color::WHITE);
+        assert!(
+            material
+                .transmittance(1.0)
+                .coarse_eq(&Color::new(0.25, 1, 0.0625))
+        );
+    }
+
+    #[test]
+    fn shadow_transmission_through_surface() {
+        let mut shape = Sphere::default();
+        let point = Point::new(0, 0, -1);
+        assert_eq!(
+            shape.material.shadow_transmission(&shape, &point, 0.0),
+            Color::BLACK
+        );
+        shape.material.color = Color::new(1, 0.5, 0);
+        shape.material.transparency = 0.8;
+        assert!(
+            shape
+                .material
+                .shadow_transmission(&shape, &point, 0.0)
+                .coarse_eq(&Color::new(0.8, 0.4, 0))
+        );
+        shape.material.casts_shadow = false;
+        shape.material.transparency = 0.0;
+        assert_eq!(
+            shape.material.shadow_transmission(&shape, &point, 0.0),
+            Color::WHITE
+        );
+    }
+
+    #[test]
+    fn smooth_material_keeps_direction() {
+        let material = Material::default();
+        let direction = Vector::new(1, 1, 0).normalized();
+        let random = &mut Random::new(0);
+        let glossy_direction = material.glossy_direction(&direction, &Vector::UP, random);
+        assert_eq!(glossy_direction, direction);
+    }
+
+    #[rstest]
+    #[case(Vector::new(1, 1, 0).normalized())]
+    #[case(Vector::new(1, -1, 0).normalized())]
+    #[case(Vector::new(1, 0.01, 0).normalized())]
+    fn rough_material_spreads_direction_on_the_same_side(#[case] direction: Vector) {
+        let material = Material {
+            roughness: 0.5,
+            ..Default::default()
+        };
+        let random = &mut Random::new(0);
+        for _ in 0..100 {
+            let glossy_direction = material.glossy_direction(&direction, &Vector::UP, random);
+            assert!(glossy_direction.magnitude().coarse_eq(&1.0));
+            assert!(glossy_direction.dot(&Vector::UP) * direction.dot(&Vector::UP) >= 0.0);
+            assert!(glossy_direction.dot(&direction) > 0.0);
+        }
     }
 
     #[test]
@@ -203,10 +439,11 @@ mod tests {
         let position = Point::ORIGIN;
         let camera = Vector::BACKWARD;
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 0, -10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, false);
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
         assert_eq!(result, Color::new(1.9, 1.9, 1.9));
     }
 
@@ -216,10 +453,11 @@ mod tests {
         let position = Point::ORIGIN;
         let camera = Vector::new(0, 2.0_f64.sqrt() / 2.0, -(2.0_f64.sqrt()) / 2.0);
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 0, -10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, false);
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
         assert_eq!(result, Color::new(1, 1, 1));
     }
 
@@ -229,10 +467,11 @@ mod tests {
         let position = Point::ORIGIN;
         let camera = Vector::BACKWARD;
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 10, -10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, false);
+        let light = PointLight::new(Point::new(0, 10, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
         assert_eq!(
             result,
             Color::new(0.7363961030678927, 0.7363961030678927, 0.7363961030678927)
@@ -245,26 +484,68 @@ mod tests {
         let position = Point::ORIGIN;
         let camera = Vector::new(0, -(2.0_f64.sqrt()) / 2.0, -(2.0_f64.sqrt()) / 2.0);
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 10, -10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, false);
+        let light = PointLight::new(Point::new(0, 10, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
         assert_eq!(
             result,
             Color::new(1.6363961030678928, 1.6363961030678928, 1.6363961030678928)
         );
     }
 
+    #[test]
+    fn lighting_with_microfacet_model_conserves_energy() {
+        let mut shape = Sphere::default();
+        shape.material.shading_model = ShadingModel::Microfacet(Microfacet::new(0, 1));
+        let position = Point::ORIGIN;
+        let camera = Vector::BACKWARD;
+        let normal = Vector::BACKWARD;
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
+        assert!(result.red > 1.0);
+        assert!(result.red < 1.1);
+    }
+
+    #[test]
+    fn direct_lighting_leaves_out_ambient_term() {
+        let shape = Sphere::default();
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let intersection = Intersection::new(4, &shape);
+        let computed_hit =
+            intersection.prepare_computations(&ray, &Intersections::from(intersection.clone()));
+        let lighting =
+            shape
+                .material
+                .lighting_from_computed_hit(&computed_hit, &light, Color::WHITE);
+        let direct =
+            shape
+                .material
+                .direct_lighting_from_computed_hit(&computed_hit, &light, Color::WHITE);
+        assert!((lighting - direct).coarse_eq(&Color::new(0.1, 0.1, 0.1)));
+        let shadowed =
+            shape
+                .material
+                .direct_lighting_from_computed_hit(&computed_hit, &light, Color::BLACK);
+        assert_eq!(shadowed, Color::BLACK);
+    }
+
     #[test]
     fn lighting_with_light_behind_surface() {
         let shape = Sphere::default();
         let position = Point::ORIGIN;
         let camera = Vector::BACKWARD;
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 0, 10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, false);
+        let light = PointLight::new(Point::new(0, 0, 10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::WHITE);
         assert_eq!(result, Color::new(0.1, 0.1, 0.1));
     }
 
@@ -274,10 +555,79 @@ mod tests {
         let position = Point::ORIGIN;
         let camera = Vector::BACKWARD;
         let normal = Vector::BACKWARD;
-        let light = Light::new(Point::new(0, 0, -10), Color::WHITE);
-        let result = shape
-            .material
-            .lighting(&shape, &light, &position, &camera, &normal, true);
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &position, &camera, &normal, Color::BLACK);
         assert_eq!(result, Color::new(0.1, 0.1, 0.1));
     }
+
+    #[test]
+    fn lighting_in_shadow_of_attenuated_light_fades_with_distance() {
+        let shape = Sphere::default();
+        let mut light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        light.attenuation = Attenuation::new(1.0, 0.1, 0.0);
+        let result = shape.material.lighting(
+            &shape,
+            &light,
+            &Point::ORIGIN,
+            &Vector::BACKWARD,
+            &Vector::BACKWARD,
+            Color::BLACK,
+        );
+        assert!(result.coarse_eq(&Color::new(0.05, 0.05, 0.05)));
+    }
+
+    #[rstest]
+    #[case(Color::WHITE, Color::new(1, 1, 1))]
+    #[case(Color::new(0.5, 0.5, 0.5), Color::new(0.55, 0.55, 0.55))]
+    #[case(Color::BLACK, Color::new(0.1, 0.1, 0.1))]
+    #[case(Color::new(1, 0.5, 0), Color::new(1, 0.55, 0.1))]
+    fn lighting_uses_light_transmission_to_attenuate_color(
+        #[case] light_transmission: Color,
+        #[case] expected: Color,
+    ) {
+        let mut shape = Sphere::default();
+        shape.material.ambient = 0.1;
+        shape.material.diffuse = 0.9;
+        shape.material.specular = 0.0;
+        let light = PointLight::new(Point::new(0, 0, -10), Color::WHITE);
+        let result = shape.material.lighting(
+            &shape,
+            &light,
+            &Point::new(0, 0, -1),
+            &Vector::BACKWARD,
+            &Vector::BACKWARD,
+            light_transmission,
+        );
+        assert!(result.coarse_eq(&expected));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0, -1), Color::new(0.9965, 0.9965, 0.9965))]
+    #[case(Point::new(0, 0.5_f64.sqrt(), -(0.5_f64.sqrt())), Color::new(0.62318, 0.62318, 0.62318))]
+    fn lighting_samples_area_light(#[case] point: Point, #[case] expected: Color) {
+        let mut light = AreaLight::new(
+            Point::new(-0.5, -0.5, -5),
+            Vector::RIGHT,
+            2,
+            Vector::UP,
+            2,
+            Color::WHITE,
+        );
+        light.jitter = false;
+        let mut shape = Sphere::default();
+        shape.material.ambient = 0.1;
+        shape.material.diffuse = 0.9;
+        shape.material.specular = 0.0;
+        let camera = (Point::new(0, 0, -5) - point).normalized();
+        let normal = Vector::new(point.x, point.y, point.z);
+        let result =
+            shape
+                .material
+                .lighting(&shape, &light, &point, &camera, &normal, Color::WHITE);
+        let difference = result - expected;
+        assert!(difference.red.abs() < 0.0001);
+    }
 }
@@ -0,0 +1,247 @@
+use crate::composites::DiffuseModel;
+use crate::consts::PI;
+use crate::primitives::{Color, Vector};
+use crate::utils::{Random, Squared};
+use core::fmt::{Display, Formatter, Result};
+
+/// Physically based shading model, surface is made of tiny mirrors oriented according to GGX
+/// distribution whose spread is given by roughness, metallic surfaces tint reflections with
+/// their color while other surfaces reflect little light and scatter the rest diffusely
+///
+/// Light intensities keep meaning they have with Phong model, white diffuse surface facing
+/// light reflects all of it, so reflectance is scaled by PI compared to BRDF
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Microfacet {
+    /// 0 for dielectrics, 1 for metals
+    pub metallic: f64,
+    /// 0 for perfectly smooth surface, 1 for completely rough
+    pub roughness: f64,
+    pub diffuse_model: DiffuseModel,
+}
+
+impl Microfacet {
+    /// Reflectance of dielectrics at normal incidence
+    pub const DIELECTRIC_REFLECTANCE: f64 = 0.04;
+
+    /// Smallest spread of microfacets, perfectly smooth distribution would reflect point
+    /// lights into single direction
+    const MIN_ALPHA: f64 = 0.001;
+
+    pub fn new(metallic: impl Into<f64>, roughness: impl Into<f64>) -> Self {
+        return Self {
+            metallic: metallic.into(),
+            roughness: roughness.into(),
+            diffuse_model: DiffuseModel::Lambert,
+        };
+    }
+
+    /// Returns reflectance of surface when viewed along its normal
+    pub fn base_reflectance(&self, surface_color: &Color) -> Color {
+        let dielectric = Color::WHITE * Self::DIELECTRIC_REFLECTANCE * (1.0 - self.metallic);
+        return dielectric + *surface_color * self.metallic;
+    }
+
+    /// Returns Schlick approximation of Fresnel reflectance for given cosine of incidence angle
+    pub fn fresnel(&self, surface_color: &Color, cos: f64) -> Color {
+        let base = self.base_reflectance(surface_color);
+        let factor = (1.0 - cos.clamp(0.0, 1.0)).powi(5);
+        return base + (Color::WHITE - base) * factor;
+    }
+
+    /// Returns color of light scattered diffusely by surface viewed from given direction
+    pub fn diffuse_albedo(
+        &self,
+        surface_color: &Color,
+        camera_direction: &Vector,
+        normal: &Vector,
+    ) -> Color {
+        let fresnel = self.fresnel(surface_color, camera_direction.dot(normal));
+        return *surface_color * (Color::WHITE - fresnel) * (1.0 - self.metallic);
+    }
+
+    /// Returns diffuse and specular fractions of light arriving from light direction which are
+    /// reflected towards camera, both already include cosine of incidence angle
+    pub fn reflectance(
+        &self,
+        surface_color: &Color,
+        light_direction: &Vector,
+        camera_direction: &Vector,
+        normal: &Vector,
+    ) -> (Color, Color) {
+        let light_dot_normal = light_direction.dot(normal);
+        let camera_dot_normal = camera_direction.dot(normal);
+        if light_dot_normal <= 0.0 || camera_dot_normal <= 0.0 {
+            return (Color::BLACK, Color::BLACK);
+        }
+        let half = (*light_direction + *camera_direction).normalized();
+        let fresnel = self.fresnel(surface_color, half.dot(camera_direction));
+
+        let diffuse_factor =
+            self.diffuse_model
+                .factor(self.roughness, light_direction, camera_direction, normal);
+        let diffuse = *surface_color
+            * (Color::WHITE - fresnel)
+            * ((1.0 - self.metallic) * diffuse_factor * light_dot_normal);
+
+        let specular_factor = PI
+            * self.distribution(half.dot(normal))
+            * self.geometry(light_dot_normal, camera_dot_normal)
+            / (4.0 * camera_dot_normal);
+        return (diffuse, fresnel * specular_factor);
+    }
+
+    /// Returns random direction of specular reflection, microfacet normals are sampled
+    /// according to their distribution, together with direction factor by which light
+    /// arriving from it is scaled is returned
+    pub fn sample_specular(
+        &self,
+        surface_color: &Color,
+        camera_direction: &Vector,
+        normal: &Vector,
+        random: &mut Random,
+    ) -> Option<(Vector, Color)> {
+        let alpha2 = self.alpha().squared();
+        let (tangent, bitangent) = normal.orthonormal_basis();
+        let random_value = random.next_f64();
+        let cos_theta = ((1.0 - random_value) / (alpha2 - 1.0).mul_add(random_value, 1.0)).sqrt();
+        let sin_theta = (1.0 - cos_theta.squared()).max(0.0).sqrt();
+        let phi = 2.0 * PI * random.next_f64();
+        let half = tangent * (sin_theta * phi.cos())
+            + bitangent * (sin_theta * phi.sin())
+            + *normal * cos_theta;
+
+        let direction = (-*camera_direction).reflect(&half);
+        let light_dot_normal = direction.dot(normal);
+        let camera_dot_normal = camera_direction.dot(normal);
+        let camera_dot_half = camera_direction.dot(&half);
+        if light_dot_normal <= 0.0 || camera_dot_normal <= 0.0 || camera_dot_half <= 0.0 {
+            return None;
+        }
+        let fresnel = self.fresnel(surface_color, camera_dot_half);
+        let weight = self.geometry(light_dot_normal, camera_dot_normal) * camera_dot_half
+            / (camera_dot_normal * cos_theta);
+        return Some((direction, fresnel * weight));
+    }
+
+    fn alpha(&self) -> f64 {
+        return self.roughness.squared().max(Self::MIN_ALPHA);
+    }
+
+    /// GGX distribution of microfacet normals
+    fn distribution(&self, normal_dot_half: f64) -> f64 {
+        let alpha2 = self.alpha().squared();
+        let denominator = normal_dot_half.squared().mul_add(alpha2 - 1.0, 1.0);
+        return alpha2 / (PI * denominator.squared());
+    }
+
+    /// Smith masking and shadowing of microfacets with Schlick GGX approximation
+    fn geometry(&self, light_dot_normal: f64, camera_dot_normal: f64) -> f64 {
+        let k = self.alpha() / 2.0;
+        let masking = |cos: f64| cos / cos.mul_add(1.0 - k, k);
+        return masking(light_dot_normal) * masking(camera_dot_normal);
+    }
+}
+
+impl Default for Microfacet {
+    fn default() -> Self {
+        return Self::new(0, 0.5);
+    }
+}
+
+impl Display for Microfacet {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("Microfacet")
+            .field("metallic", &self.metallic)
+            .field("roughness", &self.roughness)
+            .field("diffuse_model", &self.diffuse_model)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+
+    #[test]
+    fn base_reflectance_of_dielectric_and_metal() {
+        let color = Color::new(1, 0.5, 0);
+        let dielectric = Microfacet::new(0, 0.5);
+        assert!(
+            dielectric
+                .base_reflectance(&color)
+                .coarse_eq(&Color::new(0.04, 0.04, 0.04))
+        );
+        let metal = Microfacet::new(1, 0.5);
+        assert!(metal.base_reflectance(&color).coarse_eq(&color));
+    }
+
+    #[test]
+    fn surface_is_mirror_at_grazing_angle() {
+        let microfacet = Microfacet::new(0, 0.5);
+        assert!(
+            microfacet
+                .fresnel(&Color::RED, 0.0)
+                .coarse_eq(&Color::WHITE)
+        );
+    }
+
+    #[test]
+    fn dielectric_lit_from_camera_direction() {
+        let microfacet = Microfacet::new(0, 1);
+        let (diffuse, specular) =
+            microfacet.reflectance(&Color::WHITE, &Vector::UP, &Vector::UP, &Vector::UP);
+        assert!(diffuse.coarse_eq(&Color::new(0.96, 0.96, 0.96)));
+        assert!(specular.red > 0.0);
+        assert!(specular.red < 0.04);
+    }
+
+    #[test]
+    fn metal_does_not_scatter_diffusely() {
+        let microfacet = Microfacet::new(1, 0.3);
+        let light = Vector::new(1, 1, 0).normalized();
+        let (diffuse, specular) =
+            microfacet.reflectance(&Color::RED, &light, &Vector::UP, &Vector::UP);
+        assert_eq!(diffuse, Color::BLACK);
+        assert!(specular.red > specular.green);
+    }
+
+    #[test]
+    fn surface_lit_from_behind_reflects_nothing() {
+        let microfacet = Microfacet::default();
+        let reflectance =
+            microfacet.reflectance(&Color::WHITE, &Vector::DOWN, &Vector::UP, &Vector::UP);
+        assert_eq!(reflectance, (Color::BLACK, Color::BLACK));
+    }
+
+    #[test]
+    fn smooth_metal_samples_mirror_reflection() {
+        let microfacet = Microfacet::new(1, 0);
+        let camera = Vector::new(-1, 1, 0).normalized();
+        let random = &mut Random::new(0);
+        let (direction, weight) = microfacet
+            .sample_specular(&Color::RED, &camera, &Vector::UP, random)
+            .unwrap();
+        assert!((direction - Vector::new(1, 1, 0).normalized()).magnitude() < 0.01);
+        assert!(
+            (weight - Color::RED)
+                .channels()
+                .iter()
+                .all(|value| value.abs() < 0.01)
+        );
+    }
+
+    #[test]
+    fn rough_metal_does_not_create_energy() {
+        let microfacet = Microfacet::new(1, 0.7);
+        let camera = Vector::new(-1, 2, 0).normalized();
+        let random = &mut Random::new(0);
+        let samples = 1000;
+        let total = (0..samples)
+            .filter_map(|_| microfacet.sample_specular(&Color::WHITE, &camera, &Vector::UP, random))
+            .map(|(_, weight)| weight.red)
+            .sum::<f64>();
+        assert!(total / f64::from(samples) <= 1.0);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::composites::BoundingBox;
+use crate::primitives::{Point, Transformation, Vector, transformations};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+/// Movement of shape during exposure, shape moves along velocity and spins around pivot with
+/// angular velocity whose direction is axis of rotation and magnitude is angle per unit of time,
+/// the shape is at its original position at time 0
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Motion {
+    pub velocity: Vector,
+    pub angular_velocity: Vector,
+    pub pivot: Point,
+}
+
+impl Motion {
+    pub const NONE: Self = Self::new(Vector::ZERO, Vector::ZERO, Point::ORIGIN);
+
+    /// Latest time covered by [Motion::sweep], shutter times of camera are clamped to it
+    pub const END_TIME: f64 = 1.0;
+
+    pub const fn new(velocity: Vector, angular_velocity: Vector, pivot: Point) -> Self {
+        return Self {
+            velocity,
+            angular_velocity,
+            pivot,
+        };
+    }
+
+    pub fn is_static(&self) -> bool {
+        return self.velocity == Vector::ZERO && self.angular_velocity == Vector::ZERO;
+    }
+
+    /// Returns transformation moving shape from its original position to position at given time
+    pub fn transformation_at(&self, time: f64) -> Transformation {
+        let pivot = self.pivot - Point::ORIGIN;
+        let offset = pivot + self.velocity * time;
+        return transformations::translation(offset.x, offset.y, offset.z)
+            * self.rotation_at(time)
+            * transformations::translation(-pivot.x, -pivot.y, -pivot.z);
+    }
+
+    /// Returns inverse of transformation of shape at given time, static shapes get back given
+    /// inverse so they do not pay for motion
+    #[inline]
+    pub fn transformation_inverse_at(
+        &self,
+        transformation_inverse: &Transformation,
+        time: f64,
+    ) -> Transformation {
+        if self.is_static() {
+            return *transformation_inverse;
+        }
+        let pivot = self.pivot - Point::ORIGIN;
+        let offset = pivot + self.velocity * time;
+        return *transformation_inverse
+            * transformations::translation(pivot.x, pivot.y, pivot.z)
+            * self.rotation_at(-time)
+            * transformations::translation(-offset.x, -offset.y, -offset.z);
+    }
+
+    /// Returns box enclosing given bounds at every time between 0 and [Motion::END_TIME],
+    /// rotating bounds are replaced by box around sphere they sweep
+    pub fn sweep(&self, bounds: &BoundingBox) -> BoundingBox {
+        if self.is_static() || bounds.is_empty() {
+            return *bounds;
+        }
+        let swept = if self.angular_velocity == Vector::ZERO {
+            *bounds
+        } else {
+            let radius = bounds
+                .corners()
+                .iter()
+                .map(|corner| (*corner - self.pivot).magnitude())
+                .fold(0.0, f64::max);
+            BoundingBox::new(
+                self.pivot - Vector::new(radius, radius, radius),
+                self.pivot + Vector::new(radius, radius, radius),
+            )
+        };
+        let displacement = self.velocity * Self::END_TIME;
+        let translation =
+            transformations::translation(displacement.x, displacement.y, displacement.z);
+        return swept.merge(&swept.transform(&translation));
+    }
+
+    /// Returns the same motion expressed in space given by [Transformation], which is used when
+    /// moving shape is placed in transformed [Group](crate::shapes::Group)
+    pub fn transform(&self, transformation: &Transformation) -> Self {
+        let angular_velocity = if self.angular_velocity == Vector::ZERO {
+            Vector::ZERO
+        } else {
+            (*transformation * self.angular_velocity).normalized()
+                * self.angular_velocity.magnitude()
+        };
+        return Self::new(
+            *transformation * self.velocity,
+            angular_velocity,
+            *transformation * self.pivot,
+        );
+    }
+
+    fn rotation_at(&self, time: f64) -> Transformation {
+        if self.angular_velocity == Vector::ZERO {
+            return Transformation::IDENTITY;
+        }
+        let angle = self.angular_velocity.magnitude() * time;
+        return transformations::rotation(self.angular_velocity, angle);
+    }
+}
+
+impl Default for Motion {
+    fn default() -> Self {
+        return Self::NONE;
+    }
+}
+
+impl CoarseEq for Motion {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.velocity.coarse_eq(&rhs.velocity)
+                && self.angular_velocity.coarse_eq(&rhs.angular_velocity)
+                && self.pivot.coarse_eq(&rhs.pivot);
+    }
+}
+
+impl Display for Motion {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("Motion")
+            .field("velocity", &self.velocity)
+            .field("angular_velocity", &self.angular_velocity)
+            .field("pivot", &self.pivot)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::PI;
+
+    #[test]
+    fn static_motion_keeps_transformation() {
+        let transformation_inverse = transformations::scaling(2, 2, 2).inverse();
+        let result = Motion::NONE.transformation_inverse_at(&transformation_inverse, 0.7);
+        assert_eq!(result, transformation_inverse);
+        assert_eq!(
+            Motion::NONE.transformation_at(0.7),
+            Transformation::IDENTITY
+        );
+    }
+
+    #[test]
+    fn linear_motion() {
+        let motion = Motion::new(Vector::new(0, 0, 4), Vector::ZERO, Point::ORIGIN);
+        let point = motion.transformation_at(0.5) * Point::new(1, 0, 0);
+        assert_eq!(point, Point::new(1, 0, 2));
+    }
+
+    #[test]
+    fn rotational_motion_around_pivot() {
+        let motion = Motion::new(Vector::ZERO, Vector::new(0, PI, 0), Point::new(1, 0, 0));
+        let point = motion.transformation_at(1.0) * Point::new(2, 0, 0);
+        assert!(point.coarse_eq(&Point::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn inverse_at_time_undoes_motion() {
+        let transformation =
+            transformations::translation(1, 2, 3) * transformations::scaling(2, 1, 1);
+        let motion = Motion::new(
+            Vector::new(1, -2, 0.5),
+            Vector::new(0.3, 1, 0),
+            Point::new(1, 2, 3),
+        );
+        let expected = (motion.transformation_at(0.4) * transformation).inverse();
+        let result = motion.transformation_inverse_at(&transformation.inverse(), 0.4);
+        assert!(result.coarse_eq(&expected));
+    }
+
+    #[test]
+    fn transforming_motion() {
+        let motion = Motion::new(
+            Vector::new(1, 0, 0),
+            Vector::new(0, 0, 2),
+            Point::new(1, 0, 0),
+        );
+        let transformed = motion.transform(&transformations::rotation_y(PI / 2.0));
+        assert!(transformed.coarse_eq(&Motion::new(
+            Vector::new(0, 0, -1),
+            Vector::new(2, 0, 0),
+            Point::new(0, 0, -1)
+        )));
+    }
+
+    #[test]
+    fn sweeping_bounds_of_moving_shape() {
+        let bounds = BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+        let motion = Motion::new(Vector::new(3, 0, 0), Vector::ZERO, Point::ORIGIN);
+        assert_eq!(
+            motion.sweep(&bounds),
+            BoundingBox::new(Point::new(-1, -1, -1), Point::new(4, 1, 1))
+        );
+    }
+
+    #[test]
+    fn sweeping_bounds_of_rotating_shape() {
+        let bounds = BoundingBox::new(Point::new(0, 0, 0), Point::new(1, 1, 1));
+        let motion = Motion::new(Vector::ZERO, Vector::UP, Point::ORIGIN);
+        let swept = motion.sweep(&bounds);
+        let radius = 3.0_f64.sqrt();
+        assert!(swept.coarse_eq(&BoundingBox::new(
+            Point::new(-radius, -radius, -radius),
+            Point::new(radius, radius, radius)
+        )));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::composites::Material;
+use crate::primitives::{Point, Transformation, Vector};
+use crate::shapes::{Group, Shape, SmoothTriangle, Triangle};
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result};
+use std::fs;
+use std::path::Path;
+
+/// Triangle of a face, stores zero based indices into vertices and normals of [ObjParser]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Face {
+    vertices: [usize; 3],
+    normals: Option<[usize; 3]>,
+}
+
+/// Parser of Wavefront OBJ files, supports vertices, vertex normals, polygonal faces and groups,
+/// other statements are ignored
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjParser {
+    pub vertices: Vec<Point>,
+    pub normals: Vec<Vector>,
+    pub ignored_lines: usize,
+    groups: Vec<(String, Vec<Face>)>,
+}
+
+impl ObjParser {
+    pub const DEFAULT_GROUP: &'static str = "";
+
+    pub fn parse(content: &str) -> core::result::Result<Self, Box<dyn Error>> {
+        let mut parser = Self::default();
+        parser
+            .groups
+            .push((Self::DEFAULT_GROUP.to_owned(), Vec::new()));
+        let mut current_group = 0;
+
+        for line in content.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let [x, y, z] = parse_coordinates(tokens)?;
+                    parser.vertices.push(Point::new(x, y, z));
+                }
+                Some("vn") => {
+                    let [x, y, z] = parse_coordinates(tokens)?;
+                    parser.normals.push(Vector::new(x, y, z));
+                }
+                Some("f") => {
+                    let faces = parser.parse_face(tokens)?;
+                    parser.groups[current_group].1.extend(faces);
+                }
+                Some("g") => {
+                    let name = tokens.collect::<Vec<&str>>().join(" ");
+                    current_group = match parser
+                        .groups
+                        .iter()
+                        .position(|(group_name, _)| *group_name == name)
+                    {
+                        Some(index) => index,
+                        None => {
+                            parser.groups.push((name, Vec::new()));
+                            parser.groups.len() - 1
+                        }
+                    };
+                }
+                Some(_) => parser.ignored_lines += 1,
+                None => {}
+            }
+        }
+        return Ok(parser);
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> core::result::Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        return Self::parse(&content);
+    }
+
+    /// Returns names of groups in order of their first appearance, including the default group
+    pub fn group_names(&self) -> impl Iterator<Item = &str> {
+        return self.groups.iter().map(|(name, _)| name.as_str());
+    }
+
+    /// Converts parsed faces into [Group] of triangles with given [Material], faces from named
+    /// groups are placed in their own subgroups
+    pub fn to_group(&self, material: &Material) -> Group {
+        let mut group = Group::new(material.clone(), Transformation::IDENTITY);
+        for (name, faces) in &self.groups {
+            if name == Self::DEFAULT_GROUP {
+                for face in faces {
+                    group.add_child(self.triangle(face, material));
+                }
+            } else if !faces.is_empty() {
+                let mut subgroup = Group::new(material.clone(), Transformation::IDENTITY);
+                for face in faces {
+                    subgroup.add_child(self.triangle(face, material));
+                }
+                group.add_child(Box::new(subgroup));
+            }
+        }
+        return group;
+    }
+
+    /// Creates [SmoothTriangle] if face specifies vertex normals, [Triangle] otherwise
+    fn triangle(&self, face: &Face, material: &Material) -> Box<dyn Shape> {
+        let [vertex_1, vertex_2, vertex_3] = face.vertices.map(|index| self.vertices[index]);
+        if let Some(normals) = face.normals {
+            let [normal_1, normal_2, normal_3] = normals.map(|index| self.normals[index]);
+            let mut triangle =
+                SmoothTriangle::new(vertex_1, vertex_2, vertex_3, normal_1, normal_2, normal_3);
+            triangle.material = material.clone();
+            return Box::new(triangle);
+        }
+        let mut triangle = Triangle::new(vertex_1, vertex_2, vertex_3);
+        triangle.material = material.clone();
+        return Box::new(triangle);
+    }
+
+    /// Parses face statement, polygons with more than three vertices are split into fan of
+    /// triangles sharing the first vertex
+    fn parse_face<'line>(
+        &self,
+        tokens: impl Iterator<Item = &'line str>,
+    ) -> core::result::Result<Vec<Face>, Box<dyn Error>> {
+        let mut vertices = Vec::new();
+        let mut normals = Vec::new();
+        for token in tokens {
+            let mut indices = token.split('/');
+            let vertex = indices.next().unwrap_or_default();
+            vertices.push(resolve_index(vertex, self.vertices.len())?);
+            if let Some(normal) = indices.nth(1).filter(|normal| !normal.is_empty()) {
+                normals.push(resolve_index(normal, self.normals.len())?);
+            }
+        }
+        if vertices.len() < 3 {
+            return Err(format!("Face needs at least 3 vertices, got {}", vertices.len()).into());
+        }
+        let has_normals = normals.len() == vertices.len();
+
+        return Ok((1..vertices.len() - 1)
+            .map(|index| {
+                return Face {
+                    vertices: [vertices[0], vertices[index], vertices[index + 1]],
+                    normals: has_normals.then(|| [normals[0], normals[index], normals[index + 1]]),
+                };
+            })
+            .collect());
+    }
+}
+
+impl Display for ObjParser {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("ObjParser")
+            .field("vertices", &self.vertices.len())
+            .field("normals", &self.normals.len())
+            .field("groups", &self.groups.len())
+            .field("ignored_lines", &self.ignored_lines)
+            .finish();
+    }
+}
+
+fn parse_coordinates<'line>(
+    mut tokens: impl Iterator<Item = &'line str>,
+) -> core::result::Result<[f64; 3], Box<dyn Error>> {
+    let mut coordinates = [0.0; 3];
+    for coordinate in &mut coordinates {
+        *coordinate = tokens.next().ok_or("Missing coordinate")?.parse()?;
+    }
+    return Ok(coordinates);
+}
+
+/// Converts one based index, or negative index relative to the end, into zero based index
+fn resolve_index(token: &str, count: usize) -> core::result::Result<usize, Box<dyn Error>> {
+    let index: isize = token.parse()?;
+    let resolved = if index > 0 {
+        index.unsigned_abs() - 1
+    } else {
+        count.wrapping_sub(index.unsigned_abs())
+    };
+    if index == 0 || resolved >= count {
+        return Err(format!("Index {index} is out of range").into());
+    }
+    return Ok(resolved);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignoring_unrecognized_lines() {
+        let content = "There was a young lady named Bright\n\
+                       who traveled much faster than light.\n\
+                       She set out one day\n\
+                       in a relative way,\n\
+                       and came back the previous night.";
+        let parser = ObjParser::parse(content).unwrap();
+        assert_eq!(parser.ignored_lines, 5);
+        assert!(parser.vertices.is_empty());
+    }
+
+    #[test]
+    fn vertex_records() {
+        let content = "v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0\nv 1 1 0";
+        let parser = ObjParser::parse(content).unwrap();
+        assert_eq!(
+            parser.vertices,
+            vec![
+                Point::new(-1, 1, 0),
+                Point::new(-1, 0.5, 0),
+                Point::new(1, 0, 0),
+                Point::new(1, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parsing_triangle_faces() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\nf 1 2 3\nf 1 3 4";
+        let parser = ObjParser::parse(content).unwrap();
+        let faces = &parser.groups[0].1;
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0].vertices, [0, 1, 2]);
+        assert_eq!(faces[1].vertices, [0, 2, 3]);
+    }
+
+    #[test]
+    fn triangulating_polygons() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\n\nf 1 2 3 4 5";
+        let parser = ObjParser::parse(content).unwrap();
+        let faces = &parser.groups[0].1;
+        assert_eq!(faces.len(), 3);
+        assert_eq!(faces[0].vertices, [0, 1, 2]);
+        assert_eq!(faces[1].vertices, [0, 2, 3]);
+        assert_eq!(faces[2].vertices, [0, 3, 4]);
+    }
+
+    #[test]
+    fn triangles_in_groups() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                       g FirstGroup\nf 1 2 3\ng SecondGroup\nf 1 3 4";
+        let parser = ObjParser::parse(content).unwrap();
+        assert_eq!(
+            parser.group_names().collect::<Vec<&str>>(),
+            vec!["", "FirstGroup", "SecondGroup"]
+        );
+        assert_eq!(parser.groups[1].1[0].vertices, [0, 1, 2]);
+        assert_eq!(parser.groups[2].1[0].vertices, [0, 2, 3]);
+    }
+
+    #[test]
+    fn converting_obj_file_to_group() {
+        let content = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                       f 1 2 3\ng FirstGroup\nf 1 2 3\ng SecondGroup\nf 1 3 4";
+        let parser = ObjParser::parse(content).unwrap();
+        let material = Material {
+            ambient: 0.5,
+            ..Material::default()
+        };
+        let group = parser.to_group(&material);
+        assert_eq!(group.children().len(), 3);
+        assert_eq!(group.children()[0].material(), &material);
+        let expected: Box<dyn Shape> = Box::new({
+            let mut triangle = Triangle::new(
+                Point::new(-1, 1, 0),
+                Point::new(-1, 0, 0),
+                Point::new(1, 0, 0),
+            );
+            triangle.material = material.clone();
+            triangle
+        });
+        assert_eq!(group.children()[0], &expected);
+    }
+
+    #[test]
+    fn vertex_normal_records() {
+        let content = "vn 0 0 1\nvn 0.707 0 -0.707\nvn 1 2 3";
+        let parser = ObjParser::parse(content).unwrap();
+        assert_eq!(
+            parser.normals,
+            vec![
+                Vector::new(0, 0, 1),
+                Vector::new(0.707, 0, -0.707),
+                Vector::new(1, 2, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn faces_with_normals() {
+        let content = "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+                       f 1//3 2//1 3//2\nf 1/0/3 2/102/1 3/14/2\nf -3 -2 -1";
+        let parser = ObjParser::parse(content).unwrap();
+        let faces = &parser.groups[0].1;
+        assert_eq!(faces[0].normals, Some([2, 0, 1]));
+        assert_eq!(faces[1], faces[0]);
+        assert_eq!(faces[2].vertices, [0, 1, 2]);
+        assert_eq!(faces[2].normals, None);
+    }
+
+    #[test]
+    fn faces_with_normals_become_smooth_triangles() {
+        let content = "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                       vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+                       f 1//3 2//1 3//2";
+        let parser = ObjParser::parse(content).unwrap();
+        let group = parser.to_group(&Material::default());
+        let expected: Box<dyn Shape> = Box::new(SmoothTriangle::new(
+            Point::new(0, 1, 0),
+            Point::new(-1, 0, 0),
+            Point::new(1, 0, 0),
+            Vector::UP,
+            Vector::LEFT,
+            Vector::RIGHT,
+        ));
+        assert_eq!(group.children()[0], &expected);
+    }
+
+    #[test]
+    fn face_with_invalid_index() {
+        assert!(ObjParser::parse("v 0 1 0\nv -1 0 0\nf 1 2 3").is_err());
+        assert!(ObjParser::parse("v 0 1 0\nv -1 0 0\nv 1 0 0\nf 0 1 2").is_err());
+        assert!(ObjParser::parse("v 0 1 0\nv -1 0 0\nf 1 2").is_err());
+    }
+}
@@ -0,0 +1,370 @@
+use crate::composites::{ComputedHit, Intersections, Ray, ShadingModel, World};
+use crate::consts::PI;
+use crate::primitives::{Color, Vector};
+use crate::utils::Random;
+use core::fmt::{Display, Formatter, Result};
+use core::ops::Add;
+
+/// Monte Carlo integrator which follows single random path of light per camera ray, at each
+/// hit light emitted by surface and light reaching it directly from lights are added and path
+/// continues in direction chosen according to material, diffuse bounces are cosine weighted
+/// so that they follow distribution of reflected light
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathTracer {
+    /// Maximal number of bounces of single path
+    pub max_depth: u8,
+    /// Seed mixed into random generator of each sample, renders with the same seed are identical
+    pub seed: u64,
+}
+
+impl PathTracer {
+    pub const DEFAULT_MAX_DEPTH: u8 = 8;
+
+    /// Number of bounces after which paths carrying little light start to be terminated
+    /// randomly, surviving paths are strengthened so that result stays unbiased
+    pub const ROULETTE_DEPTH: u8 = 3;
+
+    const MAX_SURVIVAL_PROBABILITY: f64 = 0.95;
+
+    pub const fn new(seed: u64) -> Self {
+        return Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            seed,
+        };
+    }
+
+    pub fn color_at<'shapes>(
+        &self,
+        world: &'shapes World,
+        ray: &Ray,
+        random: &mut Random,
+        intersections: &mut Intersections<'shapes>,
+    ) -> Color {
+        let random =
+            &mut Random::new(random.next_u64() ^ self.seed.wrapping_mul(0x2545_F491_4F6C_DD1D));
+        let shadow_intersections = &mut Intersections::new();
+        let mut ray = *ray;
+        let mut throughput = Color::WHITE;
+        let mut color = World::DEFAULT_COLOR;
+
+        for depth in 0..self.max_depth {
+            world.collect_intersections(&ray, intersections);
+            let Some(hit) = intersections.hit() else {
+                color = color + throughput * world.background.color_at(&ray.direction);
+                break;
+            };
+            let computed_hit = hit.prepare_computations(&ray, intersections);
+            let material = computed_hit.shape.material();
+            throughput = throughput * computed_hit.transmittance;
+            color = color + throughput * material.emissive;
+
+            let direct_color = world
+                .lights
+                .iter()
+                .map(|light| {
+                    let light_transmission = world.light_transmission(
+                        light.as_ref(),
+                        &computed_hit.over_point,
+                        computed_hit.time,
+                        shadow_intersections,
+                    );
+                    return material.direct_lighting_from_computed_hit(
+                        &computed_hit,
+                        light.as_ref(),
+                        light_transmission,
+                    );
+                })
+                .fold(Color::BLACK, Color::add);
+            color = color + throughput * direct_color;
+
+            let Some((direction, weight)) = Self::scatter(&computed_hit, random) else {
+                break;
+            };
+            throughput = throughput * weight;
+
+            if depth >= Self::ROULETTE_DEPTH {
+                let survival_probability = Self::survival_probability(&throughput);
+                if random.next_f64() >= survival_probability {
+                    break;
+                }
+                throughput = throughput / survival_probability;
+            }
+
+            let origin = if direction.dot(&computed_hit.normal) < 0.0 {
+                computed_hit.under_point
+            } else {
+                computed_hit.over_point
+            };
+            ray = Ray::with_time(origin, direction, computed_hit.time);
+        }
+        return color;
+    }
+
+    /// Chooses how path continues from hit, reflection, refraction, glossy reflection of
+    /// [Microfacet](crate::composites::Microfacet) materials and diffuse bounce are picked
+    /// with probability proportional to their weight, returns direction of next ray and factor
+    /// by which light arriving along it is scaled
+    fn scatter(computed_hit: &ComputedHit, random: &mut Random) -> Option<(Vector, Color)> {
+        let material = computed_hit.shape.material();
+        let camera_direction = &computed_hit.camera_direction;
+        let normal = &computed_hit.normal;
+        let mut reflect_weight = material.reflectiveness;
+        let mut refract_weight = material.transparency;
+        if material.is_reflective() && material.is_transparent() {
+            let reflectance = computed_hit.schlicks_approximation();
+            reflect_weight = reflectance;
+            refract_weight = 1.0 - reflectance;
+        }
+        let surface_color = material.color_at_computed_hit(computed_hit);
+        let microfacet = match material.shading_model {
+            ShadingModel::Phong => None,
+            ShadingModel::Microfacet(microfacet) => Some(microfacet),
+        };
+        let glossy_color = microfacet.map_or(Color::BLACK, |microfacet| {
+            return microfacet.fresnel(&surface_color, camera_direction.dot(normal));
+        });
+        let diffuse_color = microfacet.map_or(surface_color * material.diffuse, |microfacet| {
+            return microfacet.diffuse_albedo(&surface_color, camera_direction, normal);
+        });
+        let glossy_weight = Self::max_channel(&glossy_color);
+        let diffuse_weight = Self::max_channel(&diffuse_color);
+
+        let total_weight = reflect_weight + refract_weight + glossy_weight + diffuse_weight;
+        if total_weight <= 0.0 {
+            return None;
+        }
+        let choice = random.next_f64() * total_weight;
+        let scale = Color::WHITE * total_weight;
+
+        if choice < reflect_weight {
+            let direction =
+                material.glossy_direction(&computed_hit.reflect_direction, normal, random);
+            return Some((direction, scale));
+        }
+        if choice < reflect_weight + refract_weight {
+            let direction = computed_hit
+                .refract_direction()
+                .unwrap_or(computed_hit.reflect_direction);
+            return Some((material.glossy_direction(&direction, normal, random), scale));
+        }
+        if choice < reflect_weight + refract_weight + glossy_weight {
+            let (direction, weight) =
+                microfacet?.sample_specular(&surface_color, camera_direction, normal, random)?;
+            return Some((direction, weight * (total_weight / glossy_weight)));
+        }
+        let direction = Self::cosine_weighted_direction(normal, random);
+        let diffuse_factor = microfacet.map_or(1.0, |microfacet| {
+            return microfacet.diffuse_model.factor(
+                microfacet.roughness,
+                &direction,
+                camera_direction,
+                normal,
+            );
+        });
+        return Some((
+            direction,
+            diffuse_color * (diffuse_factor * total_weight / diffuse_weight),
+        ));
+    }
+
+    /// Returns random direction in hemisphere around normal with density proportional to
+    /// cosine of angle from normal, which cancels cosine factor of diffuse reflection
+    fn cosine_weighted_direction(normal: &Vector, random: &mut Random) -> Vector {
+        let (tangent, bitangent) = normal.orthonormal_basis();
+        let radius = random.next_f64().sqrt();
+        let angle = 2.0 * PI * random.next_f64();
+        let height = (1.0 - radius * radius).max(0.0).sqrt();
+        return tangent * (radius * angle.cos())
+            + bitangent * (radius * angle.sin())
+            + *normal * height;
+    }
+
+    fn survival_probability(throughput: &Color) -> f64 {
+        return Self::max_channel(throughput).min(Self::MAX_SURVIVAL_PROBABILITY);
+    }
+
+    fn max_channel(color: &Color) -> f64 {
+        return color.channels().into_iter().fold(0.0, f64::max);
+    }
+}
+
+impl Default for PathTracer {
+    fn default() -> Self {
+        return Self::new(0);
+    }
+}
+
+impl Display for PathTracer {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("PathTracer")
+            .field("max_depth", &self.max_depth)
+            .field("seed", &self.seed)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composites::{Background, Material};
+    use crate::primitives::{Light, Point, PointLight, Transformation, transformations};
+    use crate::shapes::{Plane, Shape, Sphere};
+    use crate::utils::CoarseEq;
+
+    /// White sphere resting on red floor lit from above, its underside receives light only
+    /// after it bounces off the floor
+    fn floor_world() -> World {
+        let floor_material = Material {
+            color: Color::RED,
+            specular: 0.0,
+            ..Default::default()
+        };
+        let sphere_material = Material {
+            specular: 0.0,
+            ..Default::default()
+        };
+        let light: Box<dyn Light> = Box::new(PointLight::new(Point::new(0, 10, 0), Color::WHITE));
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Plane::new(floor_material, Transformation::IDENTITY)),
+            Box::new(Sphere::new(
+                sphere_material,
+                transformations::translation(0, 1, 0),
+            )),
+        ];
+        return World::new(vec![light], shapes);
+    }
+
+    fn average_color(path_tracer: &PathTracer, world: &World, ray: &Ray) -> Color {
+        let mut intersections = Intersections::new();
+        let samples = 256;
+        let color = (0..samples)
+            .map(|index| {
+                let random = &mut Random::new(index);
+                return path_tracer.color_at(world, ray, random, &mut intersections);
+            })
+            .fold(Color::BLACK, Color::add);
+        return color / samples as f64;
+    }
+
+    #[test]
+    fn default_path_tracer() {
+        let path_tracer = PathTracer::default();
+        assert_eq!(path_tracer.max_depth, PathTracer::DEFAULT_MAX_DEPTH);
+        assert_eq!(path_tracer.seed, 0);
+    }
+
+    #[test]
+    fn path_missing_shapes_is_black() {
+        let world = floor_world();
+        let ray = Ray::new(Point::new(0, 1, -5), Vector::UP);
+        let color = average_color(&PathTracer::default(), &world, &ray);
+        assert_eq!(color, World::DEFAULT_COLOR);
+    }
+
+    #[test]
+    fn background_lights_surfaces_without_lights() {
+        let floor_material = Material {
+            specular: 0.0,
+            ..Default::default()
+        };
+        let floor: Box<dyn Shape> = Box::new(Plane::new(floor_material, Transformation::IDENTITY));
+        let mut world = World::new(Vec::new(), vec![floor]);
+        world.background = Background::Color(Color::WHITE);
+        let ray = Ray::new(Point::new(0, 1, -5), Vector::new(0, -1, 5).normalized());
+        let color = average_color(&PathTracer::default(), &world, &ray);
+        assert!(color.red > 0.5);
+        assert!(color.red.coarse_eq(&color.blue));
+    }
+
+    #[test]
+    fn path_tracing_is_reproducible() {
+        let world = floor_world();
+        let ray = Ray::new(
+            Point::new(0, 0.1, -5),
+            Vector::new(0, 0.034, 4.5).normalized(),
+        );
+        let path_tracer = PathTracer::new(7);
+        let mut intersections = Intersections::new();
+        let first = path_tracer.color_at(&world, &ray, &mut Random::new(3), &mut intersections);
+        let second = path_tracer.color_at(&world, &ray, &mut Random::new(3), &mut intersections);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn seed_changes_paths() {
+        let world = floor_world();
+        let ray = Ray::new(
+            Point::new(0, 0.1, -5),
+            Vector::new(0, 0.034, 4.5).normalized(),
+        );
+        let first = average_color(&PathTracer::new(1), &world, &ray);
+        let second = average_color(&PathTracer::new(2), &world, &ray);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn diffuse_bounce_carries_color_of_lit_surface() {
+        let world = floor_world();
+        // hits underside of sphere, which faces away from light
+        let ray = Ray::new(
+            Point::new(0, 0.1, -5),
+            Vector::new(0, 0.034, 4.5).normalized(),
+        );
+        let color = average_color(&PathTracer::default(), &world, &ray);
+        assert!(color.red > 0.05);
+        assert_eq!(color.green, 0.0);
+        assert_eq!(color.blue, 0.0);
+    }
+
+    #[test]
+    fn mirror_reflects_lit_surface() {
+        let mirror_material = Material {
+            color: Color::BLACK,
+            diffuse: 0.0,
+            specular: 0.0,
+            reflectiveness: 1.0,
+            ..Default::default()
+        };
+        let mut world = floor_world();
+        world.shapes_mut()[1] = Box::new(Sphere::new(
+            mirror_material,
+            transformations::translation(0, 1, 0),
+        ));
+        // reflected down onto lit part of floor
+        let ray = Ray::new(Point::new(0, 0.5, -5), Vector::FORWARD);
+        let path_tracer = PathTracer::default();
+        let mut intersections = Intersections::new();
+        let color = path_tracer.color_at(&world, &ray, &mut Random::new(0), &mut intersections);
+        assert!(color.red > 0.0);
+        assert_eq!(color.green, 0.0);
+    }
+
+    #[test]
+    fn emissive_shape_lights_surroundings() {
+        let mut world = floor_world();
+        world.lights.clear();
+        world.shapes_mut()[1] = Box::new(Sphere::new(
+            Material {
+                emissive: Color::new(4, 4, 4),
+                ..Default::default()
+            },
+            transformations::translation(0, 2, 0),
+        ));
+        let ray = Ray::new(Point::new(0, 1, -5), Vector::new(0, -1, 5).normalized());
+        let color = average_color(&PathTracer::default(), &world, &ray);
+        assert!(color.red > 0.05);
+        assert_eq!(color.green, 0.0);
+    }
+
+    #[test]
+    fn cosine_weighted_directions_stay_in_hemisphere() {
+        let random = &mut Random::new(5);
+        let normal = Vector::new(1, 1, 0).normalized();
+        for _ in 0..100 {
+            let direction = PathTracer::cosine_weighted_direction(&normal, random);
+            assert!(direction.magnitude().coarse_eq(&1.0));
+            assert!(direction.dot(&normal) >= 0.0);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::consts::PI;
+use crate::primitives::{Point, Vector};
+use core::fmt::{Display, Formatter, Result};
+
+/// Mapping of points of canvas onto rays leaving camera, coordinates of canvas points are
+/// measured from its center in units given by [Projection::half_view]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Projection {
+    /// Rays diverge from single point through canvas placed at distance 1 from it
+    #[default]
+    Perspective,
+    /// Parallel rays start on plane of canvas, longer side of canvas covers given width
+    Orthographic { width: f64 },
+    /// Angle between ray and view direction grows linearly with distance from center of
+    /// canvas, longer side of canvas covers field of view
+    Fisheye,
+    /// Horizontal canvas coordinate is longitude and vertical one is latitude, canvas with 2:1
+    /// aspect covers whole sphere around camera
+    Equirectangular,
+}
+
+impl Projection {
+    /// Returns distance from center of canvas to middle of its longer side
+    pub fn half_view(&self, field_of_view: f64) -> f64 {
+        return match self {
+            Self::Perspective => (field_of_view / 2.0).tan(),
+            Self::Orthographic { width } => width / 2.0,
+            Self::Fisheye => field_of_view / 2.0,
+            Self::Equirectangular => PI,
+        };
+    }
+
+    /// Returns origin and direction of ray through given point of canvas in camera space, the
+    /// direction is not normalized so that perspective rays reach canvas at z = -1
+    pub fn camera_ray(&self, canvas_x: f64, canvas_y: f64) -> (Point, Vector) {
+        return match self {
+            Self::Perspective => (Point::ORIGIN, Vector::new(canvas_x, canvas_y, -1)),
+            Self::Orthographic { .. } => (Point::new(canvas_x, canvas_y, 0), Vector::BACKWARD),
+            Self::Fisheye => {
+                let angle = canvas_x.hypot(canvas_y);
+                if angle == 0.0 {
+                    return (Point::ORIGIN, Vector::BACKWARD);
+                }
+                let scale = angle.sin() / angle;
+                let direction = Vector::new(canvas_x * scale, canvas_y * scale, -angle.cos());
+                (Point::ORIGIN, direction)
+            }
+            Self::Equirectangular => {
+                let (longitude, latitude) = (canvas_x, canvas_y);
+                let direction = Vector::new(
+                    longitude.sin() * latitude.cos(),
+                    latitude.sin(),
+                    -longitude.cos() * latitude.cos(),
+                );
+                (Point::ORIGIN, direction)
+            }
+        };
+    }
+}
+
+impl Display for Projection {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return match self {
+            Self::Perspective => formatter.write_str("perspective"),
+            Self::Orthographic { width } => write!(formatter, "orthographic({width})"),
+            Self::Fisheye => formatter.write_str("fisheye"),
+            Self::Equirectangular => formatter.write_str("equirectangular"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Projection::Perspective, 1.0)]
+    #[case(Projection::Orthographic { width: 6.0 }, 3.0)]
+    #[case(Projection::Fisheye, PI / 4.0)]
+    #[case(Projection::Equirectangular, PI)]
+    fn half_view_of_projection(#[case] projection: Projection, #[case] expected: f64) {
+        assert!(projection.half_view(PI / 2.0).coarse_eq(&expected));
+    }
+
+    #[test]
+    fn orthographic_rays_are_parallel() {
+        let projection = Projection::Orthographic { width: 4.0 };
+        let (origin, direction) = projection.camera_ray(1.5, -0.5);
+        assert_eq!(origin, Point::new(1.5, -0.5, 0));
+        assert_eq!(direction, Vector::BACKWARD);
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, Vector::BACKWARD)]
+    #[case(PI / 2.0, 0.0, Vector::RIGHT)]
+    #[case(0.0, -PI / 2.0, Vector::DOWN)]
+    #[case(PI, 0.0, Vector::FORWARD)]
+    fn fisheye_angle_grows_with_distance(
+        #[case] canvas_x: f64,
+        #[case] canvas_y: f64,
+        #[case] expected: Vector,
+    ) {
+        let (origin, direction) = Projection::Fisheye.camera_ray(canvas_x, canvas_y);
+        assert_eq!(origin, Point::ORIGIN);
+        assert!(direction.coarse_eq(&expected));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0, Vector::BACKWARD)]
+    #[case(PI / 2.0, 0.0, Vector::RIGHT)]
+    #[case(-PI / 2.0, 0.0, Vector::LEFT)]
+    #[case(PI, 0.0, Vector::FORWARD)]
+    #[case(1.0, PI / 2.0, Vector::UP)]
+    fn equirectangular_maps_canvas_to_sphere(
+        #[case] canvas_x: f64,
+        #[case] canvas_y: f64,
+        #[case] expected: Vector,
+    ) {
+        let (_, direction) = Projection::Equirectangular.camera_ray(canvas_x, canvas_y);
+        assert!(direction.coarse_eq(&expected));
+        assert!(direction.magnitude().coarse_eq(&1.0));
+    }
+}
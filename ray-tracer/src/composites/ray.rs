@@ -1,6 +1,6 @@
 use crate::composites::Intersections;
 use crate::primitives::{Point, Transformation, Vector};
-use crate::shapes::{Intersect, Transform};
+use crate::shapes::Intersect;
 use crate::utils::CoarseEq;
 use core::fmt::{Display, Formatter, Result};
 
@@ -8,6 +8,8 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Ray {
     pub origin: Point,
     pub direction: Vector,
+    /// Moment at which ray is cast, moving shapes are intersected at their position at that time
+    pub time: f64,
 }
 
 impl Ray {
@@ -23,7 +25,15 @@ impl Ray {
     /// assert_eq!(ray.direction, Vector::FORWARD);
     /// ```
     pub const fn new(origin: Point, direction: Vector) -> Self {
-        return Self { origin, direction };
+        return Self::with_time(origin, direction, 0.0);
+    }
+
+    pub const fn with_time(origin: Point, direction: Vector, time: f64) -> Self {
+        return Self {
+            origin,
+            direction,
+            time,
+        };
     }
 
     #[inline]
@@ -32,20 +42,19 @@ impl Ray {
     }
 
     #[inline]
-    pub fn intersect<'shape, T: Transform + Intersect + ?Sized>(
+    pub fn intersect<'shape, T: Intersect + ?Sized>(
         &self,
         shape: &'shape T,
         intersections: &mut Intersections<'shape>,
     ) {
-        let local_ray = self.transform(&shape.transformation_inverse());
-        shape.local_intersect(&local_ray, intersections);
+        shape.intersect(self, intersections);
     }
 
     #[inline]
     pub fn transform(&self, transformation: &Transformation) -> Self {
         let new_origin = *transformation * self.origin;
         let new_direction = *transformation * self.direction;
-        return Self::new(new_origin, new_direction);
+        return Self::with_time(new_origin, new_direction, self.time);
     }
 }
 
@@ -55,6 +64,7 @@ impl Display for Ray {
             .debug_struct("Ray")
             .field("origin", &self.origin)
             .field("direction", &self.direction)
+            .field("time", &self.time)
             .finish();
     }
 }
@@ -62,13 +72,16 @@ impl Display for Ray {
 impl CoarseEq for Ray {
     fn coarse_eq(&self, rhs: &Self) -> bool {
         return std::ptr::eq(self, rhs)
-            || self.origin.coarse_eq(&rhs.origin) && self.direction.coarse_eq(&rhs.direction);
+            || self.origin.coarse_eq(&rhs.origin)
+                && self.direction.coarse_eq(&rhs.direction)
+                && self.time.coarse_eq(&rhs.time);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::composites::Motion;
     use crate::primitives::transformations;
     use crate::shapes::{Shape, Sphere, Transform};
 
@@ -165,6 +178,13 @@ mod tests {
         assert_eq!(transformed_ray.direction, Vector::UP);
     }
 
+    #[test]
+    fn transformed_ray_keeps_time() {
+        let ray = Ray::with_time(Point::new(1, 2, 3), Vector::UP, 0.25);
+        let transformed_ray = ray.transform(&transformations::scaling(2, 3, 4));
+        assert_eq!(transformed_ray.time, 0.25);
+    }
+
     #[test]
     fn ray_scaling() {
         let ray = Ray::new(Point::new(1, 2, 3), Vector::UP);
@@ -197,4 +217,18 @@ mod tests {
         ray.intersect(boxed_shape.as_ref(), &mut intersections);
         assert!(intersections.is_empty());
     }
+
+    #[test]
+    fn intersecting_moving_sphere() {
+        let mut sphere = Sphere::default();
+        sphere.motion = Motion::new(Vector::new(4, 0, 0), Vector::ZERO, Point::ORIGIN);
+        let mut intersections = Intersections::new();
+        let ray = Ray::with_time(Point::new(2, 0, -5), Vector::FORWARD, 0.0);
+        ray.intersect(&sphere, &mut intersections);
+        assert!(intersections.is_empty());
+        let ray = Ray::with_time(Point::new(2, 0, -5), Vector::FORWARD, 0.5);
+        ray.intersect(&sphere, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].distance, 4.0);
+    }
 }
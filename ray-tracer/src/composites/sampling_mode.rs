@@ -0,0 +1,116 @@
+use crate::utils::Random;
+use core::fmt::{Display, Formatter, Result};
+
+/// Placement of samples within pixel used for anti-aliasing, grid based modes round number of
+/// samples to nearest square so that pixel is divided into equal cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SamplingMode {
+    /// Samples at centers of cells of regular grid
+    #[default]
+    Grid,
+    /// Samples at random positions anywhere within pixel
+    Jittered,
+    /// Samples at random positions within cells of regular grid
+    Stratified,
+}
+
+impl SamplingMode {
+    /// Returns number of samples actually taken when given number of samples is requested
+    pub fn sample_count(&self, samples: u32) -> u32 {
+        return match self {
+            Self::Grid | Self::Stratified => Self::grid_size(samples).pow(2),
+            Self::Jittered => samples.max(1),
+        };
+    }
+
+    /// Returns offset of sample with given index from top left corner of pixel, both
+    /// coordinates are in range [0, 1)
+    pub fn offset(&self, index: u32, samples: u32, random: &mut Random) -> (f64, f64) {
+        let grid_size = Self::grid_size(samples);
+        let cell_x = f64::from(index % grid_size);
+        let cell_y = f64::from(index / grid_size);
+        let grid_size = f64::from(grid_size);
+        return match self {
+            Self::Grid => ((cell_x + 0.5) / grid_size, (cell_y + 0.5) / grid_size),
+            Self::Jittered => (random.next_f64(), random.next_f64()),
+            Self::Stratified => (
+                (cell_x + random.next_f64()) / grid_size,
+                (cell_y + random.next_f64()) / grid_size,
+            ),
+        };
+    }
+
+    fn grid_size(samples: u32) -> u32 {
+        return (f64::from(samples).sqrt().round() as u32).max(1);
+    }
+}
+
+impl Display for SamplingMode {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let name = match self {
+            Self::Grid => "grid",
+            Self::Jittered => "jittered",
+            Self::Stratified => "stratified",
+        };
+        return formatter.write_str(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(SamplingMode::Grid, 0, 1)]
+    #[case(SamplingMode::Grid, 1, 1)]
+    #[case(SamplingMode::Grid, 4, 4)]
+    #[case(SamplingMode::Grid, 5, 4)]
+    #[case(SamplingMode::Stratified, 8, 9)]
+    #[case(SamplingMode::Jittered, 0, 1)]
+    #[case(SamplingMode::Jittered, 5, 5)]
+    fn number_of_samples(
+        #[case] sampling_mode: SamplingMode,
+        #[case] samples: u32,
+        #[case] expected: u32,
+    ) {
+        assert_eq!(sampling_mode.sample_count(samples), expected);
+    }
+
+    #[rstest]
+    #[case(0, (0.25, 0.25))]
+    #[case(1, (0.75, 0.25))]
+    #[case(2, (0.25, 0.75))]
+    #[case(3, (0.75, 0.75))]
+    fn grid_offsets(#[case] index: u32, #[case] expected: (f64, f64)) {
+        let mut random = Random::new(0);
+        assert_eq!(SamplingMode::Grid.offset(index, 4, &mut random), expected);
+    }
+
+    #[test]
+    fn single_grid_sample_is_at_pixel_center() {
+        let mut random = Random::new(0);
+        assert_eq!(SamplingMode::Grid.offset(0, 1, &mut random), (0.5, 0.5));
+    }
+
+    #[test]
+    fn stratified_offsets_stay_within_their_cells() {
+        let mut random = Random::new(7);
+        for index in 0..9 {
+            let (x, y) = SamplingMode::Stratified.offset(index, 9, &mut random);
+            let (cell_x, cell_y) = (f64::from(index % 3), f64::from(index / 3));
+            assert!((cell_x / 3.0..(cell_x + 1.0) / 3.0).contains(&x));
+            assert!((cell_y / 3.0..(cell_y + 1.0) / 3.0).contains(&y));
+        }
+    }
+
+    #[test]
+    fn jittered_offsets_stay_within_pixel() {
+        let mut random = Random::new(7);
+        for index in 0..16 {
+            let (x, y) = SamplingMode::Jittered.offset(index, 16, &mut random);
+            assert!((0.0..1.0).contains(&x));
+            assert!((0.0..1.0).contains(&y));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::composites::Microfacet;
+use core::fmt::{Display, Formatter, Result};
+
+/// Model used to compute light reflected by surface of [Material](crate::composites::Material)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShadingModel {
+    /// Classic empirical model made of diffuse and specular terms of material, it is not energy
+    /// conserving
+    #[default]
+    Phong,
+    /// Physically based metallic and roughness model, diffuse, specular and shininess of
+    /// material are ignored
+    Microfacet(Microfacet),
+}
+
+impl Display for ShadingModel {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return match self {
+            Self::Phong => formatter.write_str("phong"),
+            Self::Microfacet(microfacet) => write!(formatter, "{microfacet}"),
+        };
+    }
+}
@@ -1,14 +1,20 @@
-use crate::composites::{ComputedHit, Intersections, Ray};
-use crate::primitives::{Color, Light, Point};
+use crate::composites::{Background, BoundingVolumeHierarchy, ComputedHit, Intersections, Ray};
+use crate::primitives::{Color, Light, LightSample, Point, PointLight, Vector};
 use crate::shapes::Shape;
-use crate::utils::{Squared, world_default_sphere_1, world_default_sphere_2};
+use crate::utils::{Random, world_default_sphere_1, world_default_sphere_2};
 use core::fmt::{Display, Formatter, Result};
 use core::ops::Add;
+use std::sync::OnceLock;
 
 #[derive(Debug)]
 pub struct World {
-    pub lights: Vec<Light>,
-    pub shapes: Vec<Box<dyn Shape>>,
+    pub lights: Vec<Box<dyn Light>>,
+    shapes: Vec<Box<dyn Shape>>,
+    pub background: Background,
+    /// Whether rays are intersected through [BoundingVolumeHierarchy] built lazily over shapes
+    /// instead of with every shape, which is enabled by default
+    pub use_bvh: bool,
+    bvh: OnceLock<BoundingVolumeHierarchy>,
 }
 
 impl World {
@@ -16,8 +22,38 @@ impl World {
 
     pub const DEFAULT_COLOR: Color = Color::BLACK;
 
-    pub const fn new(lights: Vec<Light>, shapes: Vec<Box<dyn Shape>>) -> Self {
-        return Self { lights, shapes };
+    /// Added to seed of rough refraction, so that its noise is not correlated with reflection
+    const REFRACTION_SEED_OFFSET: u64 = 1 << 8;
+
+    pub const fn new(lights: Vec<Box<dyn Light>>, shapes: Vec<Box<dyn Shape>>) -> Self {
+        return Self {
+            lights,
+            shapes,
+            background: Background::Color(Self::DEFAULT_COLOR),
+            use_bvh: true,
+            bvh: OnceLock::new(),
+        };
+    }
+
+    pub fn shapes(&self) -> &[Box<dyn Shape>] {
+        return &self.shapes;
+    }
+
+    /// Returns shapes for modification, [BoundingVolumeHierarchy] built over them is dropped
+    /// and rebuilt on next intersection
+    pub fn shapes_mut(&mut self) -> &mut Vec<Box<dyn Shape>> {
+        self.bvh.take();
+        return &mut self.shapes;
+    }
+
+    pub fn add_shape(&mut self, shape: Box<dyn Shape>) {
+        self.shapes_mut().push(shape);
+    }
+
+    /// Builds [BoundingVolumeHierarchy] over shapes in [World] ahead of intersecting rays instead
+    /// of on first intersection, it is rebuilt lazily whenever shapes are changed
+    pub fn build_bvh(&mut self) {
+        self.bvh = OnceLock::from(BoundingVolumeHierarchy::new(&self.shapes));
     }
 
     /// Clears intersection buffer and fills it with result of intersecting [Ray] with shapes in [World]
@@ -28,8 +64,15 @@ impl World {
         intersections: &mut Intersections<'shapes>,
     ) {
         intersections.clear();
-        for shape in &self.shapes {
-            ray.intersect(shape.as_ref(), intersections);
+        if self.use_bvh {
+            let bvh = self
+                .bvh
+                .get_or_init(|| BoundingVolumeHierarchy::new(&self.shapes));
+            bvh.intersect(ray, &self.shapes, intersections);
+        } else {
+            for shape in &self.shapes {
+                ray.intersect(shape.as_ref(), intersections);
+            }
         }
         intersections.sort();
     }
@@ -46,10 +89,19 @@ impl World {
             .lights
             .iter()
             .map(|light| {
-                let in_shadow = self.is_in_shadow(light, &computed_hit.over_point, intersections);
-                return material.lighting_from_computed_hit(computed_hit, light, in_shadow);
+                let light_transmission = self.light_transmission(
+                    light.as_ref(),
+                    &computed_hit.over_point,
+                    computed_hit.time,
+                    intersections,
+                );
+                return material.lighting_from_computed_hit(
+                    computed_hit,
+                    light.as_ref(),
+                    light_transmission,
+                );
             })
-            .fold(Self::DEFAULT_COLOR, Color::add);
+            .fold(material.emissive, Color::add);
 
         let reflected_color =
             self.reflected_color(computed_hit, intersections, remaining_iterations);
@@ -80,9 +132,11 @@ impl World {
             .hit()
             .map(|hit| {
                 let computed_hit = &hit.prepare_computations(ray, intersections);
-                return self.shade_hit(computed_hit, shading_intersections, remaining_iterations);
+                let color =
+                    self.shade_hit(computed_hit, shading_intersections, remaining_iterations);
+                return color * computed_hit.transmittance;
             })
-            .unwrap_or(Self::DEFAULT_COLOR);
+            .unwrap_or_else(|| self.background.color_at(&ray.direction));
     }
 
     #[inline]
@@ -94,21 +148,89 @@ impl World {
         return self.internal_color_at(ray, intersections, Self::MAX_REFLECTION_ITERATIONS);
     }
 
-    /// Returns whether between the [Light] and [Point] is shape casting shadow
-    fn is_in_shadow<'shapes>(
+    /// Returns fraction of light of [Light] which reaches [Point] at given time averaged over
+    /// light samples, transparent shapes in the way filter light through their color
+    pub(crate) fn light_transmission<'shapes>(
         &'shapes self,
-        light: &Light,
+        light: &dyn Light,
         point: &Point,
+        time: f64,
         intersections: &mut Intersections<'shapes>,
-    ) -> bool {
-        let light_direction = light.position - *point;
-        let light_distance = light_direction.magnitude();
-        let shadow_ray = Ray::new(*point, light_direction.normalized());
+    ) -> Color {
+        let sample_count = light.sample_count();
+        let transmission = (0..sample_count)
+            .map(|index| {
+                let sample = light.sample(point, index);
+                return self.sample_transmission(&sample, point, time, intersections);
+            })
+            .fold(Color::BLACK, Color::add);
+        return transmission / sample_count as f64;
+    }
+
+    /// Returns fraction of light of [LightSample] which passes through shapes between it and
+    /// [Point], opaque shape casting shadow blocks it completely, transparent shape filters
+    /// light once for each pass through it and light is absorbed by the innermost shape along
+    /// the way like it is for rays seen by camera
+    fn sample_transmission<'shapes>(
+        &'shapes self,
+        sample: &LightSample,
+        point: &Point,
+        time: f64,
+        intersections: &mut Intersections<'shapes>,
+    ) -> Color {
+        let shadow_ray = Ray::with_time(*point, sample.direction, time);
         self.collect_intersections(&shadow_ray, intersections);
-        return intersections.into_iter().any(|intersection| {
-            return intersection.shape.material().casts_shadow
-                && intersection.is_within_distance(light_distance);
-        });
+        let mut transmission = Color::WHITE;
+        // shapes containing current part of shadow ray with distances at which it entered them
+        let mut containers: Vec<(&dyn Shape, f64)> = Vec::new();
+        let mut previous_distance = 0.0;
+        for intersection in intersections.iter() {
+            if !intersection.shape.material().casts_shadow {
+                continue;
+            }
+            let distance = intersection.distance.min(sample.distance);
+            if distance > previous_distance {
+                if let Some((shape, _)) = containers.last() {
+                    let absorption = shape.material().transmittance(distance - previous_distance);
+                    transmission = transmission * absorption;
+                }
+                previous_distance = distance;
+            }
+            if intersection.distance >= sample.distance {
+                break;
+            }
+
+            let position = containers
+                .iter()
+                .position(|(shape, _)| *shape == intersection.shape);
+            let entry_distance = match position {
+                Some(index) => containers.remove(index).1,
+                None => {
+                    containers.push((intersection.shape, intersection.distance));
+                    intersection.distance
+                }
+            };
+            // surface is crossed on entry, or on exit when shape was entered behind point
+            let is_crossing = position.is_none() || entry_distance < 0.0;
+            if intersection.distance >= 0.0 && is_crossing {
+                let position = shadow_ray.position(intersection.distance);
+                let material = intersection.shape.material();
+                transmission = transmission
+                    * material.shadow_transmission(intersection.shape, &position, time);
+            }
+            if transmission == Color::BLACK {
+                return transmission;
+            }
+        }
+        if previous_distance < sample.distance
+            && let Some((shape, _)) = containers.last()
+        {
+            let absorption = shape
+                .material()
+                .transmittance(sample.distance - previous_distance);
+            transmission = transmission * absorption;
+        }
+        return transmission;
     }
 
     fn reflected_color<'shapes>(
@@ -121,9 +243,14 @@ impl World {
             return Self::DEFAULT_COLOR;
         }
 
-        let reflected_ray = Ray::new(computed_hit.over_point, computed_hit.reflect_direction);
-        let reflected_color =
-            self.internal_color_at(&reflected_ray, intersections, remaining_iterations - 1);
+        let reflected_color = self.glossy_color(
+            computed_hit,
+            &computed_hit.over_point,
+            &computed_hit.reflect_direction,
+            intersections,
+            remaining_iterations,
+            0,
+        );
         return reflected_color * computed_hit.shape.material().reflectiveness;
     }
 
@@ -137,29 +264,62 @@ impl World {
             return Self::DEFAULT_COLOR;
         }
 
-        let n_ratio = computed_hit.refractive_index_1 / computed_hit.refractive_index_2;
-        let cos_i = computed_hit.camera_direction.dot(&computed_hit.normal);
-        let sin2_t = n_ratio.squared() * (1.0 - cos_i.squared());
-        let is_total_internal_reflection = sin2_t > 1.0;
-
-        if is_total_internal_reflection {
+        let Some(direction) = computed_hit.refract_direction() else {
             return Self::DEFAULT_COLOR;
-        }
-
-        let cos_t = (1.0 - sin2_t).sqrt();
-        let direction = computed_hit.normal * n_ratio.mul_add(cos_i, -cos_t)
-            - (computed_hit.camera_direction * n_ratio);
-        let refracted_ray = Ray::new(computed_hit.under_point, direction);
-        let refracted_color =
-            self.internal_color_at(&refracted_ray, intersections, remaining_iterations - 1);
+        };
+        let refracted_color = self.glossy_color(
+            computed_hit,
+            &computed_hit.under_point,
+            &direction,
+            intersections,
+            remaining_iterations,
+            Self::REFRACTION_SEED_OFFSET,
+        );
 
         return refracted_color * computed_hit.shape.material().transparency;
     }
+
+    /// Returns color seen in given direction from hit, rough materials spread rays around it,
+    /// several rays are averaged only at hits seen directly by camera and deeper hits use a
+    /// single spread ray, so that number of rays does not grow exponentially with depth, seed
+    /// offset keeps random spread of reflection and refraction independent
+    fn glossy_color<'shapes>(
+        &'shapes self,
+        computed_hit: &ComputedHit,
+        origin: &Point,
+        direction: &Vector,
+        intersections: &mut Intersections<'shapes>,
+        remaining_iterations: u8,
+        seed_offset: u64,
+    ) -> Color {
+        let material = computed_hit.shape.material();
+        if material.is_smooth() {
+            let ray = Ray::with_time(*origin, *direction, computed_hit.time);
+            return self.internal_color_at(&ray, intersections, remaining_iterations - 1);
+        }
+
+        let sample_count = if remaining_iterations == Self::MAX_REFLECTION_ITERATIONS {
+            material.glossy_samples.max(1)
+        } else {
+            1
+        };
+        let seed = u64::from(remaining_iterations) + seed_offset;
+        let mut random = Random::from_point(&computed_hit.point, seed);
+        let color = (0..sample_count)
+            .map(|_| {
+                let direction =
+                    material.glossy_direction(direction, &computed_hit.normal, &mut random);
+                let ray = Ray::with_time(*origin, direction, computed_hit.time);
+                return self.internal_color_at(&ray, intersections, remaining_iterations - 1);
+            })
+            .fold(Self::DEFAULT_COLOR, Color::add);
+        return color / f64::from(sample_count);
+    }
 }
 
 impl Default for World {
     fn default() -> Self {
-        let light = Light::default();
+        let light: Box<dyn Light> = Box::new(PointLight::default());
         let shapes: Vec<Box<dyn Shape>> = vec![
             Box::new(world_default_sphere_1()),
             Box::new(world_default_sphere_2()),
@@ -173,6 +333,7 @@ impl PartialEq for World {
         return std::ptr::eq(self, rhs)
             || self.lights.len() == rhs.lights.len()
                 && self.shapes.len() == rhs.shapes.len()
+                && self.background == rhs.background
                 && self.lights.iter().all(|light| rhs.lights.contains(light))
                 && self.shapes.iter().all(|shape| {
                     return rhs.shapes.iter().any(|entry| entry == shape);
@@ -186,6 +347,7 @@ impl Display for World {
             .debug_struct("World")
             .field("light", &self.lights)
             .field("shapes", &self.shapes)
+            .field("background", &self.background)
             .finish();
     }
 }
@@ -196,23 +358,24 @@ mod tests {
     use crate::composites::{Intersection, Intersections, Material, Ray};
     use crate::consts::PI;
     use crate::patterns::TestPattern;
-    use crate::primitives::Vector;
     use crate::primitives::transformations;
-    use crate::shapes::{Plane, Shape, Sphere, Transform};
+    use crate::primitives::{AreaLight, DirectionalLight, SpotLight, Vector};
+    use crate::shapes::{Cube, Plane, Shape, Sphere, Transform};
     use crate::utils::CoarseEq;
+    use rstest::rstest;
     use std::sync::Arc;
 
     #[test]
     fn default_world() {
         let world = World::default();
-        let light = Light::new(Point::new(-10, 10, -10), Color::new(1, 1, 1));
+        let light = PointLight::new(Point::new(-10, 10, -10), Color::new(1, 1, 1));
         let mut sphere_1 = Sphere::default();
         sphere_1.material.color = Color::new(0.8, 1, 0.6);
         sphere_1.material.diffuse = 0.7;
         sphere_1.material.specular = 0.2;
         let mut sphere_2 = Sphere::default();
         sphere_2.set_transformation(transformations::scaling(0.5, 0.5, 0.5));
-        assert_eq!(world.lights[0], light);
+        assert!(world.lights[0].as_ref() == &light as &dyn Light);
         assert_eq!(world.shapes.len(), 2);
         assert!(
             world
@@ -259,6 +422,101 @@ mod tests {
         assert_eq!(intersections[3].distance, 6.0);
     }
 
+    fn world_with_many_shapes() -> World {
+        let mut world = World::default();
+        world.shapes.push(Box::new(Plane::new(
+            Material::default(),
+            transformations::translation(0, -1, 0),
+        )));
+        for index in 0..30 {
+            let mut sphere = Sphere::default();
+            sphere.material.color = Color::new(index, 0, 0);
+            sphere.set_transformation(
+                transformations::translation(index % 6 - 3, index / 6 - 2, index % 4)
+                    * transformations::scaling(0.4, 0.4, 0.4),
+            );
+            world.shapes.push(Box::new(sphere));
+        }
+        return world;
+    }
+
+    impl World {
+        fn intersect_ray(&self, ray: &Ray) -> Vec<f64> {
+            let mut intersections = Intersections::new();
+            self.collect_intersections(ray, &mut intersections);
+            return intersections
+                .iter()
+                .map(|intersection| intersection.distance)
+                .collect();
+        }
+    }
+
+    fn assert_bvh_matches_brute_force(world: &World, world_with_bvh: &World, rays: &[Ray]) {
+        for ray in rays {
+            let mut expected = Intersections::new();
+            world.collect_intersections(ray, &mut expected);
+            let mut intersections = Intersections::new();
+            world_with_bvh.collect_intersections(ray, &mut intersections);
+            assert!(!intersections.is_empty());
+            assert_eq!(intersections.len(), expected.len());
+            for (intersection, expected) in intersections.iter().zip(expected.iter()) {
+                assert_eq!(intersection.distance, expected.distance);
+                assert_eq!(intersection.shape, expected.shape);
+            }
+        }
+    }
+
+    #[test]
+    fn intersections_with_bvh_match_brute_force() {
+        let mut world = world_with_many_shapes();
+        world.use_bvh = false;
+        let world_with_bvh = world_with_many_shapes();
+        let rays = [
+            Ray::new(Point::new(0, 0, -5), Vector::FORWARD),
+            Ray::new(Point::new(-4, 3, -5), Vector::new(1, -0.5, 1).normalized()),
+            Ray::new(
+                Point::new(2, 0.1, 1),
+                Vector::new(-1, 0.2, 0.3).normalized(),
+            ),
+            Ray::new(Point::new(0, 10, 0), Vector::DOWN),
+        ];
+        assert_bvh_matches_brute_force(&world, &world_with_bvh, &rays);
+    }
+
+    #[test]
+    fn intersections_of_nearly_axis_aligned_rays_with_bvh_match_brute_force() {
+        let mut world = world_with_many_shapes();
+        world.use_bvh = false;
+        let world_with_bvh = world_with_many_shapes();
+        let rays = [
+            Ray::new(
+                Point::new(0, 1, -1e8),
+                Vector::new(0, -1e-8, 1).normalized(),
+            ),
+            Ray::new(
+                Point::new(-1e8, 0.5, 3),
+                Vector::new(1, -5e-9, 0).normalized(),
+            ),
+        ];
+        assert_bvh_matches_brute_force(&world, &world_with_bvh, &rays);
+    }
+
+    #[test]
+    fn changing_shapes_rebuilds_bvh() {
+        let mut world = world_with_many_shapes();
+        world.build_bvh();
+        let ray = Ray::new(Point::new(10, 0, -5), Vector::FORWARD);
+        assert!(world.intersect_ray(&ray).is_empty());
+        world.add_shape(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(10, 0, 0),
+        )));
+        assert_eq!(world.intersect_ray(&ray).len(), 2);
+        world.shapes_mut().truncate(2);
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        assert_eq!(world.intersect_ray(&ray).len(), 4);
+    }
+
     #[test]
     fn shading_intersection() {
         let world = World::default();
@@ -280,7 +538,10 @@ mod tests {
     #[test]
     fn shading_intersection_from_inside() {
         let mut world = World::default();
-        world.lights = vec![Light::new(Point::new(0, 0.25, 0), Color::WHITE)];
+        world.lights = vec![Box::new(PointLight::new(
+            Point::new(0, 0.25, 0),
+            Color::WHITE,
+        ))];
         let ray = Ray::new(Point::ORIGIN, Vector::FORWARD);
         let intersection = Intersection::new(0.5, world.shapes[1].as_ref());
         let intersections = Intersections::new();
@@ -291,6 +552,18 @@ mod tests {
         assert!(color.coarse_eq(&expected));
     }
 
+    #[test]
+    fn shading_adds_emitted_light() {
+        let mut world = World::default();
+        world.lights.clear();
+        let mut sphere = Sphere::default();
+        sphere.material.emissive = Color::new(2, 1, 0.5);
+        world.shapes[0] = Box::new(sphere);
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let color = world.color_at(&ray, &mut Intersections::new());
+        assert_eq!(color, Color::new(2, 1, 0.5));
+    }
+
     #[test]
     fn color_when_ray_misses() {
         let world = World::default();
@@ -299,6 +572,34 @@ mod tests {
         assert_eq!(color, World::DEFAULT_COLOR);
     }
 
+    #[test]
+    fn color_when_ray_misses_comes_from_background() {
+        let mut world = World::new(Vec::new(), Vec::new());
+        world.background = Background::Gradient {
+            bottom: Color::WHITE,
+            top: Color::BLUE,
+        };
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::UP);
+        let color = world.color_at(&ray, &mut Intersections::new());
+        assert_eq!(color, Color::BLUE);
+    }
+
+    #[test]
+    fn reflection_picks_up_background() {
+        let mut plane = Plane::default();
+        plane.material = Material {
+            color: Color::BLACK,
+            ambient: 0.0,
+            reflectiveness: 1.0,
+            ..Default::default()
+        };
+        let mut world = World::new(Vec::new(), vec![Box::new(plane)]);
+        world.background = Background::Color(Color::RED);
+        let ray = Ray::new(Point::new(0, 1, -1), Vector::new(0, -1, 1).normalized());
+        let color = world.color_at(&ray, &mut Intersections::new());
+        assert!(color.coarse_eq(&Color::RED));
+    }
+
     #[test]
     fn color_when_ray_hits() {
         let world = World::default();
@@ -336,39 +637,191 @@ mod tests {
     #[test]
     fn no_shadow_when_nothing_obscures_light() {
         let world = World::default();
+        let light_position = Point::new(-10, 10, -10);
         let point = Point::new(0, 10, 0);
+        let sample = LightSample::towards(&point, &light_position, Color::WHITE);
         let mut intersections = Intersections::new();
-        assert!(!world.is_in_shadow(&world.lights[0], &point, &mut intersections));
+        assert_eq!(
+            world.sample_transmission(&sample, &point, 0.0, &mut intersections),
+            Color::WHITE
+        );
     }
 
     #[test]
     fn no_shadow_when_light_is_behind_point() {
         let world = World::default();
+        let light_position = Point::new(-10, 10, -10);
         let point = Point::new(-20, 20, -20);
+        let sample = LightSample::towards(&point, &light_position, Color::WHITE);
         let mut intersections = Intersections::new();
-        assert!(!world.is_in_shadow(&world.lights[0], &point, &mut intersections));
+        assert_eq!(
+            world.sample_transmission(&sample, &point, 0.0, &mut intersections),
+            Color::WHITE
+        );
     }
 
     #[test]
     fn no_shadow_when_object_is_behind_point() {
         let world = World::default();
+        let light_position = Point::new(-10, 10, -10);
         let point = Point::new(-2, 2, -2);
+        let sample = LightSample::towards(&point, &light_position, Color::WHITE);
         let mut intersections = Intersections::new();
-        assert!(!world.is_in_shadow(&world.lights[0], &point, &mut intersections));
+        assert_eq!(
+            world.sample_transmission(&sample, &point, 0.0, &mut intersections),
+            Color::WHITE
+        );
     }
 
     #[test]
     fn shadow_when_object_is_between_hit_and_light() {
         let world = World::default();
+        let light_position = Point::new(-10, 10, -10);
         let point = Point::new(10, -10, 10);
+        let sample = LightSample::towards(&point, &light_position, Color::WHITE);
+        let mut intersections = Intersections::new();
+        assert_eq!(
+            world.sample_transmission(&sample, &point, 0.0, &mut intersections),
+            Color::BLACK
+        );
+    }
+
+    #[test]
+    fn transparent_shapes_cast_colored_shadows() {
+        let mut world = World::default();
+        let mut glass = Sphere::new(
+            Material {
+                color: Color::new(1, 0.5, 0.2),
+                transparency: 0.8,
+                ..Default::default()
+            },
+            transformations::translation(0, 0, -3),
+        );
+        let point = Point::new(0, 0, -6);
+        let light = PointLight::new(Point::ORIGIN, Color::WHITE);
+        world.shapes = vec![Box::new(glass.clone())];
+        let mut intersections = Intersections::new();
+        let transmission = world.light_transmission(&light, &point, 0.0, &mut intersections);
+        // light is filtered once when entering glass, and not again when leaving it
+        let expected = glass.material.color * glass.material.transparency;
+        assert!(transmission.coarse_eq(&expected));
+        assert!(transmission.coarse_eq(&Color::new(0.8, 0.4, 0.16)));
+        glass.material.transparency = 0.0;
+        world.shapes = vec![Box::new(glass)];
+        let transmission = world.light_transmission(&light, &point, 0.0, &mut Intersections::new());
+        assert_eq!(transmission, Color::BLACK);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0, -6))]
+    #[case(Point::new(0, 0, -3))]
+    fn thicker_absorbing_shapes_cast_darker_shadows(#[case] point: Point) {
+        let shadow = |thickness: f64| {
+            let mut world = World::default();
+            let glass = Cube::new(
+                Material {
+                    transparency: 1.0,
+                    absorption_color: Color::new(0.2, 0.8, 0.5),
+                    absorption_density: 1.0,
+                    ..Default::default()
+                },
+                transformations::translation(0, 0, -3) * transformations::scaling(2, 2, thickness),
+            );
+            world.shapes = vec![Box::new(glass)];
+            let light = PointLight::new(Point::new(0, 0, 3), Color::WHITE);
+            return world.light_transmission(&light, &point, 0.0, &mut Intersections::new());
+        };
+        let thin_shadow = shadow(0.5);
+        let thick_shadow = shadow(1.0);
+        let distance = if point.z < -4.0 { 1.0 } else { 0.5 };
+        let expected = Color::new(0.2, 0.8, 0.5).map(|value| value.powf(distance));
+        assert!(thin_shadow.coarse_eq(&expected));
+        let expected = Color::new(0.2, 0.8, 0.5).map(|value| value.powf(2.0 * distance));
+        assert!(thick_shadow.coarse_eq(&expected));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 1.0001, 0), 1.0)]
+    #[case(Point::new(-1.0001, 0, 0), 1.0)]
+    #[case(Point::new(0, 0, -1.0001), 1.0)]
+    #[case(Point::new(0, 0, 1.0001), 0.0)]
+    #[case(Point::new(1.0001, 0, 0), 0.0)]
+    #[case(Point::new(0, -1.0001, 0), 0.0)]
+    #[case(Point::ORIGIN, 0.0)]
+    fn point_light_visibility(#[case] point: Point, #[case] expected: f64) {
+        let world = World::default();
+        let mut intersections = Intersections::new();
+        let transmission =
+            world.light_transmission(world.lights[0].as_ref(), &point, 0.0, &mut intersections);
+        assert_eq!(transmission, Color::WHITE * expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0, 2), 0.0)]
+    #[case(Point::new(1, -1, 2), 0.25)]
+    #[case(Point::new(1.5, 0, 2), 0.5)]
+    #[case(Point::new(1.25, 1.25, 3), 0.75)]
+    #[case(Point::new(0, 0, -2), 1.0)]
+    fn area_light_visibility(#[case] point: Point, #[case] expected: f64) {
+        let world = World::default();
+        let mut light = AreaLight::new(
+            Point::new(-0.5, -0.5, -5),
+            Vector::RIGHT,
+            2,
+            Vector::UP,
+            2,
+            Color::WHITE,
+        );
+        light.jitter = false;
         let mut intersections = Intersections::new();
-        assert!(world.is_in_shadow(&world.lights[0], &point, &mut intersections));
+        let transmission = world.light_transmission(&light, &point, 0.0, &mut intersections);
+        assert_eq!(transmission, Color::WHITE * expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 2, 0), 1.0)]
+    #[case(Point::new(0, -2, 0), 0.0)]
+    #[case(Point::new(0, -200, 0), 0.0)]
+    #[case(Point::new(3, -2, 0), 1.0)]
+    fn directional_light_visibility(#[case] point: Point, #[case] expected: f64) {
+        let world = World::default();
+        let light = DirectionalLight::new(Vector::DOWN, Color::WHITE);
+        let mut intersections = Intersections::new();
+        let transmission = world.light_transmission(&light, &point, 0.0, &mut intersections);
+        assert_eq!(transmission, Color::WHITE * expected);
+    }
+
+    #[test]
+    fn shade_hit_with_spot_light() {
+        let spot_light = |direction: Vector| -> Box<dyn Light> {
+            return Box::new(SpotLight::new(
+                Point::new(0, 0, -10),
+                direction,
+                PI / 8.0,
+                0,
+                Color::WHITE,
+            ));
+        };
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut world = World {
+            lights: vec![spot_light(Vector::FORWARD)],
+            ..World::default()
+        };
+        let lit_color = world.color_at(&ray, &mut Intersections::new());
+        world.lights = vec![spot_light(Vector::UP)];
+        let unlit_color = world.color_at(&ray, &mut Intersections::new());
+        let ambient = world.shapes[0].material().color * world.shapes[0].material().ambient;
+        assert!(unlit_color.coarse_eq(&ambient));
+        assert!(lit_color.red > unlit_color.red);
     }
 
     #[test]
     fn shade_hit_is_given_intersection_in_shadow() {
         let mut world = World::default();
-        world.lights = vec![Light::new(Point::new(0, 0, -10), Color::WHITE)];
+        world.lights = vec![Box::new(PointLight::new(
+            Point::new(0, 0, -10),
+            Color::WHITE,
+        ))];
         world.shapes.push(Box::<Sphere>::default());
         let mut sphere = Sphere::default();
         sphere.set_transformation(transformations::translation(0, 0, 10));
@@ -450,7 +903,10 @@ mod tests {
     fn no_infinite_recursion_in_reflections() {
         let mut world = World::default();
         world.shapes = Vec::new();
-        world.lights = vec![Light::new(Point::ORIGIN, Color::new(1, 1, 1))];
+        world.lights = vec![Box::new(PointLight::new(
+            Point::ORIGIN,
+            Color::new(1, 1, 1),
+        ))];
         let mut lower = Plane::default();
         lower.material.reflectiveness = 1.0;
         lower.set_transformation(transformations::translation(0, -1, 0));
@@ -543,6 +999,94 @@ mod tests {
         assert_eq!(color, World::DEFAULT_COLOR);
     }
 
+    fn reflected_color_on_plane(roughness: f64, glossy_samples: u32) -> Color {
+        let mut world = World::default();
+        let mut shape = Plane::default();
+        shape.material.reflectiveness = 0.5;
+        shape.material.roughness = roughness;
+        shape.material.glossy_samples = glossy_samples;
+        shape.set_transformation(transformations::translation(0, -1, 0));
+        world.shapes.push(Box::new(shape.clone()));
+        let ray = Ray::new(
+            Point::new(0, 0, -3),
+            Vector::new(0, -(2.0_f64.sqrt()) / 2.0, 2.0_f64.sqrt() / 2.0),
+        );
+        let intersection = Intersection::new(2.0_f64.sqrt(), &shape);
+        let computed_hit = intersection.prepare_computations(&ray, &Intersections::new());
+        return world.reflected_color(
+            &computed_hit,
+            &mut Intersections::new(),
+            World::MAX_REFLECTION_ITERATIONS,
+        );
+    }
+
+    #[test]
+    fn rough_reflection_averages_spread_rays() {
+        let smooth_color = reflected_color_on_plane(0.0, 8);
+        let single_color = reflected_color_on_plane(0.5, 1);
+        let rough_color = reflected_color_on_plane(0.5, 8);
+        assert_ne!(rough_color, smooth_color);
+        assert_ne!(rough_color, single_color);
+        assert!(rough_color.green > 0.0);
+        assert_eq!(rough_color, reflected_color_on_plane(0.5, 8));
+    }
+
+    #[test]
+    fn rough_refraction_blurs_shapes_behind_surface() {
+        let world = World::default();
+        let mut glass = Plane::new(
+            Material::glass(),
+            transformations::translation(0, 0, -2) * transformations::rotation_x(PI / 2.0),
+        );
+        let ray = Ray::new(Point::new(0.9, 0, -5), Vector::FORWARD);
+        let refracted_color = |glass: &Plane| {
+            let intersection = Intersection::new(3, glass);
+            let intersections = Intersections::from(intersection.clone());
+            let computed_hit = intersection.prepare_computations(&ray, &intersections);
+            return world.refracted_color(
+                &computed_hit,
+                &mut Intersections::new(),
+                World::MAX_REFLECTION_ITERATIONS,
+            );
+        };
+        let clear_color = refracted_color(&glass);
+        glass.material.roughness = 0.5;
+        let frosted_color = refracted_color(&glass);
+        assert_ne!(frosted_color, clear_color);
+        assert!(frosted_color.green > 0.0);
+    }
+
+    #[rstest]
+    #[case(0.1)]
+    #[case(1.0)]
+    fn glass_absorbs_light_according_to_thickness(#[case] thickness: f64) {
+        let glass_color = |absorption_density: f64| {
+            let mut world = World::default();
+            // glass which neither bends nor reflects light, so that only absorption changes color
+            let material = Material {
+                color: Color::BLACK,
+                ambient: 0.0,
+                specular: 0.0,
+                transparency: 1.0,
+                absorption_color: Color::new(0.2, 0.8, 0.5),
+                absorption_density,
+                ..Default::default()
+            };
+            let glass = Cube::new(
+                material,
+                transformations::translation(0, 0, -3) * transformations::scaling(2, 2, thickness),
+            );
+            world.shapes.push(Box::new(glass));
+            let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+            return world.color_at(&ray, &mut Intersections::new());
+        };
+        let clear_color = glass_color(0.0);
+        let tinted_color = glass_color(1.0);
+        let expected =
+            clear_color * Color::new(0.2, 0.8, 0.5).map(|value| value.powf(2.0 * thickness));
+        assert!(tinted_color.coarse_eq(&expected));
+    }
+
     #[test]
     fn refracted_color_with_refracted_ray() {
         let mut world = World::default();
@@ -594,7 +1138,8 @@ mod tests {
         intersections.push(Intersection::new(2.0_f64.sqrt(), arc_floor.as_ref()));
         let computed_hit = intersections[0].prepare_computations(&ray, &intersections);
         let color = world.shade_hit(&computed_hit, &mut Intersections::new(), 5);
-        let expected = Color::new(0.9364253889815014, 0.6864253889815014, 0.6864253889815014);
+        // ball is lit through half transparent floor instead of being fully in its shadow
+        let expected = Color::new(1.1254657843754603, 0.6864253865273064, 0.6864253865273064);
         assert!(color.coarse_eq(&expected));
     }
 
@@ -624,7 +1169,7 @@ mod tests {
         intersections.push(Intersection::new(2.0_f64.sqrt(), boxed_floor.as_ref()));
         let computed_hit = intersections[0].prepare_computations(&ray, &intersections);
         let color = world.shade_hit(&computed_hit, &mut Intersections::new(), 5);
-        let expected = Color::new(0.9339151412754023, 0.696434227200244, 0.692430691912747);
+        let expected = Color::new(1.1150027443556947, 0.6964342245147331, 0.6924306893197625);
         assert!(color.coarse_eq(&expected));
     }
 }
@@ -1,14 +1,54 @@
+pub use blend_pattern::BlendPattern;
 pub use checker_pattern::CheckerPattern;
 pub use complex_pattern::ComplexPattern;
+pub use cube_map_pattern::CubeMapPattern;
 pub use gradient_pattern::GradientPattern;
+pub use marble_pattern::MarblePattern;
+pub use multi_gradient_pattern::MultiGradientPattern;
+pub use nested_pattern::NestedPattern;
+pub use noise_perturbation::NoisePerturbation;
+pub use normal_map_perturbation::NormalMapPerturbation;
+pub use normal_perturbation::NormalPerturbation;
 pub use pattern::Pattern;
 pub(crate) use pattern::TestPattern;
+pub use perlin_noise::PerlinNoise;
+pub use perturbed_pattern::PerturbedPattern;
+pub use radial_gradient_pattern::RadialGradientPattern;
 pub use ring_pattern::RingPattern;
+pub use solid_pattern::SolidPattern;
 pub use stripe_pattern::StripePattern;
+pub use texture_map_pattern::TextureMapPattern;
+pub use turbulence_pattern::TurbulencePattern;
+pub use uv_align_check_pattern::UvAlignCheckPattern;
+pub use uv_checker_pattern::UvCheckerPattern;
+pub use uv_image_pattern::{UvImagePattern, WrapMode};
+pub use uv_mapping::{CubeFace, UvMapping};
+pub use uv_pattern::UvPattern;
+pub use wood_pattern::WoodPattern;
 
+mod blend_pattern;
 mod checker_pattern;
 mod complex_pattern;
+mod cube_map_pattern;
 mod gradient_pattern;
+mod marble_pattern;
+mod multi_gradient_pattern;
+mod nested_pattern;
+mod noise_perturbation;
+mod normal_map_perturbation;
+mod normal_perturbation;
 mod pattern;
+mod perlin_noise;
+mod perturbed_pattern;
+mod radial_gradient_pattern;
 mod ring_pattern;
+mod solid_pattern;
 mod stripe_pattern;
+mod texture_map_pattern;
+mod turbulence_pattern;
+mod uv_align_check_pattern;
+mod uv_checker_pattern;
+mod uv_image_pattern;
+mod uv_mapping;
+mod uv_pattern;
+mod wood_pattern;
@@ -0,0 +1,108 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Weighted average of two patterns, each of them is evaluated in its own space
+#[derive(Clone, Debug)]
+pub struct BlendPattern {
+    pattern_a: Arc<dyn Pattern>,
+    pattern_b: Arc<dyn Pattern>,
+    /// Share of second pattern in the result, 0.5 gives plain average
+    pub weight: f64,
+    transformation_inverse: Transformation,
+}
+
+impl BlendPattern {
+    pub const fn new(pattern_a: Arc<dyn Pattern>, pattern_b: Arc<dyn Pattern>) -> Self {
+        return Self {
+            pattern_a,
+            pattern_b,
+            weight: 0.5,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for BlendPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let color_a = self.pattern_a.color_at_nested(point);
+        let color_b = self.pattern_b.color_at_nested(point);
+        return color_a * (1.0 - self.weight) + color_b * self.weight;
+    }
+}
+
+impl Transform for BlendPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for BlendPattern {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.pattern_a.as_ref() == rhs.pattern_a.as_ref()
+                && self.pattern_b.as_ref() == rhs.pattern_b.as_ref()
+                && self.weight == rhs.weight
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for BlendPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("BlendPattern")
+            .field("pattern_a", &self.pattern_a)
+            .field("pattern_b", &self.pattern_b)
+            .field("weight", &self.weight)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{SolidPattern, StripePattern, TestPattern};
+    use crate::primitives::transformations;
+
+    #[test]
+    fn blending_averages_patterns() {
+        let mut stripes = StripePattern::new(Color::WHITE, Color::BLACK);
+        stripes.set_transformation(transformations::scaling(0.5, 1, 1));
+        let pattern =
+            BlendPattern::new(Arc::new(stripes), Arc::new(SolidPattern::new(Color::BLUE)));
+        assert_eq!(
+            pattern.color_at(&Point::new(0.25, 0, 0)),
+            Color::new(0.5, 0.5, 1)
+        );
+        assert_eq!(
+            pattern.color_at(&Point::new(0.75, 0, 0)),
+            Color::new(0, 0, 0.5)
+        );
+    }
+
+    #[test]
+    fn weight_of_blend() {
+        let mut pattern = BlendPattern::new(
+            Arc::new(TestPattern::new()),
+            Arc::new(StripePattern::new(Color::WHITE, Color::WHITE)),
+        );
+        pattern.weight = 0.25;
+        let color = pattern.color_at(&Point::new(1, 0, 0));
+        assert_eq!(color, Color::new(1, 0.25, 0.25));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::patterns::{CubeFace, Pattern, UvPattern};
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Applies different [UvPattern] to each face of cube from -1 to 1
+#[derive(Clone, Debug)]
+pub struct CubeMapPattern {
+    faces: [Arc<dyn UvPattern>; 6],
+    transformation_inverse: Transformation,
+}
+
+impl CubeMapPattern {
+    /// Creates pattern from patterns of faces given in order of [CubeFace::ALL]
+    pub const fn new(faces: [Arc<dyn UvPattern>; 6]) -> Self {
+        return Self {
+            faces,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+
+    pub fn face(&self, face: CubeFace) -> &dyn UvPattern {
+        return self.faces[face as usize].as_ref();
+    }
+}
+
+impl Pattern for CubeMapPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let face = CubeFace::of(point);
+        let (u, v) = face.map(point);
+        return self.face(face).uv_color_at(u, v);
+    }
+}
+
+impl Transform for CubeMapPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for CubeMapPattern {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self
+                .faces
+                .iter()
+                .zip(&rhs.faces)
+                .all(|(face, rhs_face)| face.as_ref() == rhs_face.as_ref())
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for CubeMapPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("CubeMapPattern")
+            .field("faces", &self.faces)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::UvAlignCheckPattern;
+    use rstest::rstest;
+
+    const RED: Color = Color::RED;
+    const YELLOW: Color = Color {
+        red: 1.0,
+        green: 1.0,
+        blue: 0.0,
+    };
+    const BROWN: Color = Color {
+        red: 1.0,
+        green: 0.5,
+        blue: 0.0,
+    };
+    const GREEN: Color = Color::GREEN;
+    const CYAN: Color = Color {
+        red: 0.0,
+        green: 1.0,
+        blue: 1.0,
+    };
+    const BLUE: Color = Color::BLUE;
+    const PURPLE: Color = Color {
+        red: 1.0,
+        green: 0.0,
+        blue: 1.0,
+    };
+    const WHITE: Color = Color::WHITE;
+
+    fn align_check(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> Arc<dyn UvPattern> {
+        return Arc::new(UvAlignCheckPattern::new(
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        ));
+    }
+
+    #[rstest]
+    #[case(Point::new(-1, 0, 0), YELLOW)]
+    #[case(Point::new(-1, 0.9, -0.9), CYAN)]
+    #[case(Point::new(-1, 0.9, 0.9), RED)]
+    #[case(Point::new(-1, -0.9, -0.9), BLUE)]
+    #[case(Point::new(-1, -0.9, 0.9), BROWN)]
+    #[case(Point::new(0, 0, 1), CYAN)]
+    #[case(Point::new(-0.9, 0.9, 1), RED)]
+    #[case(Point::new(0.9, 0.9, 1), YELLOW)]
+    #[case(Point::new(-0.9, -0.9, 1), BROWN)]
+    #[case(Point::new(0.9, -0.9, 1), GREEN)]
+    #[case(Point::new(1, 0, 0), RED)]
+    #[case(Point::new(1, 0.9, 0.9), YELLOW)]
+    #[case(Point::new(1, 0.9, -0.9), PURPLE)]
+    #[case(Point::new(1, -0.9, 0.9), GREEN)]
+    #[case(Point::new(1, -0.9, -0.9), WHITE)]
+    #[case(Point::new(0, 0, -1), GREEN)]
+    #[case(Point::new(0.9, 0.9, -1), PURPLE)]
+    #[case(Point::new(-0.9, 0.9, -1), CYAN)]
+    #[case(Point::new(0.9, -0.9, -1), WHITE)]
+    #[case(Point::new(-0.9, -0.9, -1), BLUE)]
+    #[case(Point::new(0, 1, 0), BROWN)]
+    #[case(Point::new(-0.9, 1, -0.9), CYAN)]
+    #[case(Point::new(0.9, 1, -0.9), PURPLE)]
+    #[case(Point::new(-0.9, 1, 0.9), RED)]
+    #[case(Point::new(0.9, 1, 0.9), YELLOW)]
+    #[case(Point::new(0, -1, 0), PURPLE)]
+    #[case(Point::new(-0.9, -1, 0.9), BROWN)]
+    #[case(Point::new(0.9, -1, 0.9), GREEN)]
+    #[case(Point::new(-0.9, -1, -0.9), BLUE)]
+    #[case(Point::new(0.9, -1, -0.9), WHITE)]
+    fn finding_colors_on_mapped_cube(#[case] point: Point, #[case] expected: Color) {
+        let pattern = CubeMapPattern::new([
+            align_check(YELLOW, CYAN, RED, BLUE, BROWN),
+            align_check(CYAN, RED, YELLOW, BROWN, GREEN),
+            align_check(RED, YELLOW, PURPLE, GREEN, WHITE),
+            align_check(GREEN, PURPLE, CYAN, WHITE, BLUE),
+            align_check(BROWN, CYAN, PURPLE, RED, YELLOW),
+            align_check(PURPLE, BROWN, GREEN, BLUE, WHITE),
+        ]);
+        assert_eq!(pattern.color_at(&point), expected);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::consts::PI;
+use crate::patterns::{Pattern, PerlinNoise};
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Veins of color b running across color a along planes perpendicular to x axis, the veins are
+/// twisted by turbulence
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarblePattern {
+    color_a: Color,
+    color_b: Color,
+    noise: PerlinNoise,
+    /// How much turbulence distorts veins, 0 gives regular sine bands
+    pub distortion: f64,
+    pub octaves: u32,
+    transformation_inverse: Transformation,
+}
+
+impl MarblePattern {
+    pub fn new(color_a: Color, color_b: Color, noise: PerlinNoise) -> Self {
+        return Self {
+            color_a,
+            color_b,
+            noise,
+            distortion: 1.0,
+            octaves: 4,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for MarblePattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let turbulence = self.noise.turbulence(point, self.octaves);
+        let phase = self.distortion.mul_add(turbulence, point.x) * PI;
+        let fraction = phase.sin().mul_add(0.5, 0.5);
+        return self.color_a + (self.color_b - self.color_a) * fraction;
+    }
+}
+
+impl Transform for MarblePattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for MarblePattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("MarblePattern")
+            .field("color_a", &self.color_a)
+            .field("color_b", &self.color_b)
+            .field("seed", &self.noise.seed())
+            .field("distortion", &self.distortion)
+            .field("octaves", &self.octaves)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undistorted_marble_forms_sine_bands() {
+        let mut pattern = MarblePattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+        pattern.distortion = 0.0;
+        assert_eq!(pattern.color_at(&Point::ORIGIN), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(pattern.color_at(&Point::new(0.5, 0.3, 0.7)), Color::WHITE);
+        assert_eq!(pattern.color_at(&Point::new(1.5, 0.3, 0.7)), Color::BLACK);
+    }
+
+    #[test]
+    fn turbulence_distorts_marble() {
+        let pattern = MarblePattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+        let point = Point::new(0.5, 0.3, 0.7);
+        assert_ne!(pattern.color_at(&point), Color::WHITE);
+        let color = pattern.color_at(&Point::new(0.25, 0.4, 0.1));
+        assert!((0.0..=1.0).contains(&color.red));
+    }
+}
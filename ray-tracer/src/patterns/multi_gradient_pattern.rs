@@ -0,0 +1,101 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Gradient along x axis passing through any number of colors, each stop places color at given
+/// x coordinate, points before first and after last stop keep color of that stop
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiGradientPattern {
+    stops: Vec<(f64, Color)>,
+    transformation_inverse: Transformation,
+}
+
+impl MultiGradientPattern {
+    /// Creates gradient from stops given in any order, without stops pattern is black
+    pub fn new(mut stops: Vec<(f64, Color)>) -> Self {
+        stops.sort_by(|(position_a, _), (position_b, _)| position_a.total_cmp(position_b));
+        return Self {
+            stops,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+
+    pub fn stops(&self) -> &[(f64, Color)] {
+        return &self.stops;
+    }
+}
+
+impl Pattern for MultiGradientPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let next = self
+            .stops
+            .partition_point(|(position, _)| *position <= point.x);
+        if next == 0 {
+            return self.stops.first().map_or(Color::BLACK, |(_, color)| *color);
+        }
+        let (position_a, color_a) = self.stops[next - 1];
+        let Some(&(position_b, color_b)) = self.stops.get(next) else {
+            return color_a;
+        };
+        let fraction = (point.x - position_a) / (position_b - position_a);
+        return color_a + (color_b - color_a) * fraction;
+    }
+}
+
+impl Transform for MultiGradientPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for MultiGradientPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("MultiGradientPattern")
+            .field("stops", &self.stops)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(-1.0, Color::RED)]
+    #[case(0.0, Color::RED)]
+    #[case(0.25, Color::new(0.5, 0.5, 0))]
+    #[case(0.5, Color::GREEN)]
+    #[case(0.75, Color::new(0, 0.5, 0.5))]
+    #[case(1.0, Color::BLUE)]
+    #[case(2.0, Color::BLUE)]
+    fn multi_gradient_interpolates_between_stops(#[case] x: f64, #[case] expected: Color) {
+        let pattern = MultiGradientPattern::new(vec![
+            (1.0, Color::BLUE),
+            (0.0, Color::RED),
+            (0.5, Color::GREEN),
+        ]);
+        assert_eq!(pattern.color_at(&Point::new(x, 0, 0)), expected);
+    }
+
+    #[test]
+    fn multi_gradient_without_stops() {
+        let pattern = MultiGradientPattern::new(Vec::new());
+        assert_eq!(pattern.color_at(&Point::ORIGIN), Color::BLACK);
+    }
+}
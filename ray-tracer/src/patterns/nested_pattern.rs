@@ -0,0 +1,121 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Uses layout of one pattern to mix two other patterns, layout pattern should go from black to
+/// white and its red channel is used as share of second pattern, so for example stripes of
+/// black and white alternate between given patterns
+#[derive(Clone, Debug)]
+pub struct NestedPattern {
+    layout: Arc<dyn Pattern>,
+    pattern_a: Arc<dyn Pattern>,
+    pattern_b: Arc<dyn Pattern>,
+    transformation_inverse: Transformation,
+}
+
+impl NestedPattern {
+    pub const fn new(
+        layout: Arc<dyn Pattern>,
+        pattern_a: Arc<dyn Pattern>,
+        pattern_b: Arc<dyn Pattern>,
+    ) -> Self {
+        return Self {
+            layout,
+            pattern_a,
+            pattern_b,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for NestedPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let fraction = self.layout.color_at_nested(point).red.clamp(0.0, 1.0);
+        if fraction <= 0.0 {
+            return self.pattern_a.color_at_nested(point);
+        }
+        if fraction >= 1.0 {
+            return self.pattern_b.color_at_nested(point);
+        }
+        let color_a = self.pattern_a.color_at_nested(point);
+        let color_b = self.pattern_b.color_at_nested(point);
+        return color_a + (color_b - color_a) * fraction;
+    }
+}
+
+impl Transform for NestedPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for NestedPattern {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.layout.as_ref() == rhs.layout.as_ref()
+                && self.pattern_a.as_ref() == rhs.pattern_a.as_ref()
+                && self.pattern_b.as_ref() == rhs.pattern_b.as_ref()
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for NestedPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("NestedPattern")
+            .field("layout", &self.layout)
+            .field("pattern_a", &self.pattern_a)
+            .field("pattern_b", &self.pattern_b)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{CheckerPattern, GradientPattern, SolidPattern, StripePattern};
+    use crate::primitives::transformations;
+
+    #[test]
+    fn checkers_of_stripes() {
+        let layout = CheckerPattern::new(Color::BLACK, Color::WHITE);
+        let mut stripes = StripePattern::new(Color::RED, Color::BLUE);
+        stripes.set_transformation(transformations::scaling(0.25, 0.25, 0.25));
+        let pattern = NestedPattern::new(
+            Arc::new(layout),
+            Arc::new(stripes),
+            Arc::new(SolidPattern::new(Color::GREEN)),
+        );
+        assert_eq!(pattern.color_at(&Point::new(0.1, 0, 0)), Color::RED);
+        assert_eq!(pattern.color_at(&Point::new(0.3, 0, 0)), Color::BLUE);
+        assert_eq!(pattern.color_at(&Point::new(1.3, 0, 0)), Color::GREEN);
+    }
+
+    #[test]
+    fn gradient_layout_mixes_patterns() {
+        let pattern = NestedPattern::new(
+            Arc::new(GradientPattern::new(Color::BLACK, Color::WHITE)),
+            Arc::new(SolidPattern::new(Color::RED)),
+            Arc::new(SolidPattern::new(Color::BLUE)),
+        );
+        assert_eq!(
+            pattern.color_at(&Point::new(0.25, 0, 0)),
+            Color::new(0.75, 0, 0.25)
+        );
+    }
+}
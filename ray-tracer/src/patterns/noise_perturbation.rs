@@ -0,0 +1,86 @@
+use crate::patterns::{NormalPerturbation, PerlinNoise};
+use crate::primitives::{Point, Transformation, Vector};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Tilts normal by noise sampled separately for each axis, which gives surface bumpy look
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoisePerturbation {
+    noise: PerlinNoise,
+    /// Largest change of each component of normal
+    pub scale: f64,
+    transformation_inverse: Transformation,
+}
+
+impl NoisePerturbation {
+    pub fn new(noise: PerlinNoise, scale: impl Into<f64>) -> Self {
+        return Self {
+            noise,
+            scale: scale.into(),
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl NormalPerturbation for NoisePerturbation {
+    fn perturb(&self, point: &Point, normal: &Vector) -> Vector {
+        // points sampled for y and z are far apart, so components are not correlated
+        let offset = Vector::new(
+            self.noise.noise(point),
+            self.noise.noise(&(*point + Vector::new(17.3, 0, 0))),
+            self.noise.noise(&(*point + Vector::new(0, 0, 23.9))),
+        );
+        return (*normal + offset * self.scale).normalized();
+    }
+}
+
+impl Transform for NoisePerturbation {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for NoisePerturbation {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("NoisePerturbation")
+            .field("seed", &self.noise.seed())
+            .field("scale", &self.scale)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+
+    #[test]
+    fn zero_scale_keeps_normal() {
+        let perturbation = NoisePerturbation::new(PerlinNoise::default(), 0);
+        let normal = perturbation.perturb(&Point::new(0.3, 0.5, 0.7), &Vector::UP);
+        assert_eq!(normal, Vector::UP);
+    }
+
+    #[test]
+    fn noise_tilts_normal() {
+        let perturbation = NoisePerturbation::new(PerlinNoise::default(), 0.5);
+        let normal = perturbation.perturb(&Point::new(0.3, 0.5, 0.7), &Vector::UP);
+        assert!(normal.magnitude().coarse_eq(&1.0));
+        assert!(normal.coarse_ne(&Vector::UP));
+        assert!(normal.dot(&Vector::UP) > 0.5);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::patterns::{NormalPerturbation, UvMapping, UvPattern};
+use crate::primitives::{Point, Transformation, Vector};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Replaces normal with one read from tangent space normal map, red, green and blue channels
+/// store tangent, bitangent and normal components scaled from [-1, 1] to [0, 1]
+///
+/// Tangent frame is derived from normal in space of perturbation alone, so it turns together with
+/// shape, tangent runs horizontally around y axis and bitangent points upwards, which matches
+/// spherical and cylindrical mappings, surfaces facing straight up or down use x axis as tangent
+#[derive(Clone, Debug)]
+pub struct NormalMapPerturbation {
+    normal_map: Arc<dyn UvPattern>,
+    mapping: UvMapping,
+    /// Scales tangent components of normal, 0 flattens map and values above 1 exaggerate it
+    pub strength: f64,
+    transformation_inverse: Transformation,
+}
+
+impl NormalMapPerturbation {
+    pub const fn new(normal_map: Arc<dyn UvPattern>, mapping: UvMapping) -> Self {
+        return Self {
+            normal_map,
+            mapping,
+            strength: 1.0,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl NormalPerturbation for NormalMapPerturbation {
+    fn perturb(&self, point: &Point, normal: &Vector) -> Vector {
+        let (u, v) = self.mapping.map(point);
+        let color = self.normal_map.uv_color_at(u, v);
+        let tangent_normal = Vector::new(
+            color.red.mul_add(2.0, -1.0) * self.strength,
+            color.green.mul_add(2.0, -1.0) * self.strength,
+            color.blue.mul_add(2.0, -1.0),
+        );
+
+        let tangent = Vector::UP.cross(normal);
+        let tangent = if tangent.magnitude() < 1e-6 {
+            Vector::RIGHT
+        } else {
+            tangent.normalized()
+        };
+        let bitangent = normal.cross(&tangent);
+        let perturbed =
+            tangent * tangent_normal.x + bitangent * tangent_normal.y + *normal * tangent_normal.z;
+        return perturbed.normalized();
+    }
+}
+
+impl Transform for NormalMapPerturbation {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for NormalMapPerturbation {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.normal_map.as_ref() == rhs.normal_map.as_ref()
+                && self.mapping == rhs.mapping
+                && self.strength == rhs.strength
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for NormalMapPerturbation {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("NormalMapPerturbation")
+            .field("normal_map", &self.normal_map)
+            .field("mapping", &self.mapping)
+            .field("strength", &self.strength)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composites::Canvas;
+    use crate::patterns::UvImagePattern;
+    use crate::primitives::Color;
+    use crate::utils::CoarseEq;
+
+    fn uniform_normal_map(color: Color) -> NormalMapPerturbation {
+        let mut canvas = Canvas::new(1, 1);
+        canvas.set_pixel(0, 0, color);
+        return NormalMapPerturbation::new(
+            Arc::new(UvImagePattern::new(canvas)),
+            UvMapping::Spherical,
+        );
+    }
+
+    #[test]
+    fn flat_normal_map_keeps_normal() {
+        let perturbation = uniform_normal_map(Color::new(0.5, 0.5, 1));
+        let normal = Vector::new(0, 0, -1);
+        let perturbed = perturbation.perturb(&Point::new(0, 0, -1), &normal);
+        assert!(perturbed.coarse_eq(&normal));
+    }
+
+    #[test]
+    fn normal_map_tilts_towards_tangent_and_bitangent() {
+        let tilted = Color::new(1, 0.5, 0.5);
+        let perturbation = uniform_normal_map(tilted);
+        let perturbed = perturbation.perturb(&Point::new(0, 0, -1), &Vector::BACKWARD);
+        assert!(perturbed.coarse_eq(&Vector::UP.cross(&Vector::BACKWARD)));
+        let perturbation = uniform_normal_map(Color::new(0.5, 1, 0.5));
+        let perturbed = perturbation.perturb(&Point::new(0, 0, -1), &Vector::BACKWARD);
+        assert!(perturbed.coarse_eq(&Vector::UP));
+    }
+
+    #[test]
+    fn normal_map_on_horizontal_surface() {
+        let perturbation = uniform_normal_map(Color::new(1, 0.5, 0.5));
+        let perturbed = perturbation.perturb(&Point::new(0, 1, 0), &Vector::UP);
+        assert!(perturbed.coarse_eq(&Vector::RIGHT));
+    }
+
+    #[test]
+    fn zero_strength_flattens_normal_map() {
+        let mut perturbation = uniform_normal_map(Color::new(1, 0.5, 1));
+        perturbation.strength = 0.0;
+        let perturbed = perturbation.perturb(&Point::new(0, 0, -1), &Vector::BACKWARD);
+        assert!(perturbed.coarse_eq(&Vector::BACKWARD));
+    }
+}
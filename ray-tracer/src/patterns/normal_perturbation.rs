@@ -0,0 +1,36 @@
+use crate::dyn_partial_eq::DynPartialEq;
+use crate::primitives::{Point, Vector};
+use crate::shapes::{Shape, Transform};
+use core::fmt::{Debug, Display};
+
+/// Changes normal used for shading without changing geometry of shape, like bumps of orange peel
+/// or grooves between bricks, it is sampled in its own space just like [Pattern](super::Pattern)
+pub trait NormalPerturbation: Transform + Debug + Display + Send + Sync + DynPartialEq {
+    /// Returns perturbed normal at point in space of perturbation, normal is in the same space
+    /// and normalized
+    fn perturb(&self, point: &Point, normal: &Vector) -> Vector;
+
+    /// Returns perturbed world space normal at point of shape, point and normal are brought into
+    /// space of perturbation so that bumps turn together with shape, time is needed to find where
+    /// moving shape was
+    #[inline]
+    fn perturb_at_shape(
+        &self,
+        shape: &dyn Shape,
+        point: &Point,
+        normal: &Vector,
+        time: f64,
+    ) -> Vector {
+        let world_to_local = self.transformation_inverse() * shape.transformation_inverse_at(time);
+        let local_point = world_to_local * *point;
+        let local_normal = (world_to_local.inverse().transpose() * *normal).normalized();
+        let perturbed = self.perturb(&local_point, &local_normal);
+        return (world_to_local.transpose() * perturbed).normalized();
+    }
+}
+
+impl PartialEq for dyn NormalPerturbation {
+    fn eq(&self, other: &Self) -> bool {
+        return self.dyn_eq(DynPartialEq::as_any(other));
+    }
+}
@@ -6,12 +6,20 @@ use core::fmt::{Debug, Display, Formatter};
 pub trait Pattern: Transform + Debug + Display + Send + Sync + DynPartialEq {
     fn color_at(&self, point: &Point) -> Color;
 
+    /// Returns color at point of shape, time is needed to find where moving shape was
     #[inline]
-    fn color_at_shape(&self, shape: &dyn Shape, point: &Point) -> Color {
-        let object_point = shape.transformation_inverse() * *point;
+    fn color_at_shape(&self, shape: &dyn Shape, point: &Point, time: f64) -> Color {
+        let object_point = shape.transformation_inverse_at(time) * *point;
         let pattern_point = self.transformation_inverse() * object_point;
         return self.color_at(&pattern_point);
     }
+
+    /// Returns color at point given in space of enclosing pattern, used by patterns built from
+    /// other patterns
+    #[inline]
+    fn color_at_nested(&self, point: &Point) -> Color {
+        return self.color_at(&(self.transformation_inverse() * *point));
+    }
 }
 
 impl PartialEq for dyn Pattern {
@@ -101,7 +109,7 @@ mod tests {
         let mut sphere = Sphere::default();
         sphere.set_transformation(transformations::scaling(2, 2, 2));
         let pattern = TestPattern::new();
-        let color = pattern.color_at_shape(&sphere, &Point::new(2, 3, 4));
+        let color = pattern.color_at_shape(&sphere, &Point::new(2, 3, 4), 0.0);
         assert_eq!(color, Color::new(1, 1.5, 2));
     }
 
@@ -110,7 +118,7 @@ mod tests {
         let sphere = Sphere::default();
         let mut pattern = TestPattern::new();
         pattern.set_transformation(transformations::scaling(2, 2, 2));
-        let color = pattern.color_at_shape(&sphere, &Point::new(2, 3, 4));
+        let color = pattern.color_at_shape(&sphere, &Point::new(2, 3, 4), 0.0);
         assert_eq!(color, Color::new(1, 1.5, 2));
     }
 
@@ -120,7 +128,7 @@ mod tests {
         sphere.set_transformation(transformations::scaling(2, 2, 2));
         let mut pattern = TestPattern::new();
         pattern.set_transformation(transformations::translation(0.5, 1, 1.5));
-        let color = pattern.color_at_shape(&sphere, &Point::new(2.5, 3, 3.5));
+        let color = pattern.color_at_shape(&sphere, &Point::new(2.5, 3, 3.5), 0.0);
         assert_eq!(color, Color::new(0.75, 0.5, 0.25));
     }
 }
@@ -0,0 +1,166 @@
+use crate::primitives::Point;
+use crate::utils::Random;
+
+/// Deterministic 3D gradient noise (improved Perlin noise), the permutation of lattice gradients
+/// is shuffled with given seed so that differently seeded noises are uncorrelated
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerlinNoise {
+    seed: u64,
+    permutation: [u8; 512],
+}
+
+impl PerlinNoise {
+    pub fn new(seed: u64) -> Self {
+        let mut values: [u8; 256] = core::array::from_fn(|index| index as u8);
+        let mut random = Random::new(seed);
+        for index in (1..values.len()).rev() {
+            let other = (random.next_u64() % (index as u64 + 1)) as usize;
+            values.swap(index, other);
+        }
+        return Self {
+            seed,
+            permutation: core::array::from_fn(|index| values[index % 256]),
+        };
+    }
+
+    pub const fn seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Returns smoothly varying value in range [-1, 1], it is 0 at every point with integer
+    /// coordinates
+    pub fn noise(&self, point: &Point) -> f64 {
+        let (x, y, z) = (point.x.floor(), point.y.floor(), point.z.floor());
+        let (cell_x, cell_y, cell_z) = (
+            (x as i64 & 255) as usize,
+            (y as i64 & 255) as usize,
+            (z as i64 & 255) as usize,
+        );
+        let (x, y, z) = (point.x - x, point.y - y, point.z - z);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        let p = &self.permutation;
+        let a = usize::from(p[cell_x]) + cell_y;
+        let aa = usize::from(p[a]) + cell_z;
+        let ab = usize::from(p[a + 1]) + cell_z;
+        let b = usize::from(p[cell_x + 1]) + cell_y;
+        let ba = usize::from(p[b]) + cell_z;
+        let bb = usize::from(p[b + 1]) + cell_z;
+
+        return lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, gradient(p[aa], x, y, z), gradient(p[ba], x - 1.0, y, z)),
+                lerp(
+                    u,
+                    gradient(p[ab], x, y - 1.0, z),
+                    gradient(p[bb], x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    gradient(p[aa + 1], x, y, z - 1.0),
+                    gradient(p[ba + 1], x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    gradient(p[ab + 1], x, y - 1.0, z - 1.0),
+                    gradient(p[bb + 1], x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        );
+    }
+
+    /// Returns sum of absolute values of noise over given number of octaves, each octave has
+    /// double frequency and half amplitude of previous one
+    pub fn turbulence(&self, point: &Point, octaves: u32) -> f64 {
+        let mut sum = 0.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            sum += self.noise(&(*point * frequency)).abs() / frequency;
+            frequency *= 2.0;
+        }
+        return sum;
+    }
+}
+
+impl Default for PerlinNoise {
+    fn default() -> Self {
+        return Self::new(0);
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    return t.mul_add(b - a, a);
+}
+
+/// Returns dot product of offset and one of 12 gradients pointing to edges of cube
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let hash = hash & 15;
+    let u = if hash < 8 { x } else { y };
+    let v = if hash < 4 {
+        y
+    } else if hash == 12 || hash == 14 {
+        x
+    } else {
+        z
+    };
+    let u = if hash & 1 == 0 { u } else { -u };
+    let v = if hash & 2 == 0 { v } else { -v };
+    return u + v;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+
+    #[test]
+    fn noise_is_zero_at_lattice_points() {
+        let noise = PerlinNoise::default();
+        for point in [Point::ORIGIN, Point::new(1, 2, 3), Point::new(-4, 7, -1)] {
+            assert!(noise.noise(&point).coarse_eq(&0.0));
+        }
+    }
+
+    #[test]
+    fn noise_is_deterministic_and_bounded() {
+        let noise = PerlinNoise::new(7);
+        let point = Point::new(1.3, -2.7, 0.45);
+        assert_eq!(noise.noise(&point), PerlinNoise::new(7).noise(&point));
+        assert_ne!(noise.noise(&point), PerlinNoise::new(8).noise(&point));
+        let mut random = Random::new(1);
+        for _ in 0..1000 {
+            let point = Point::new(
+                random.next_f64() * 100.0 - 50.0,
+                random.next_f64() * 100.0 - 50.0,
+                random.next_f64() * 100.0 - 50.0,
+            );
+            assert!((-1.0..=1.0).contains(&noise.noise(&point)));
+        }
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        let noise = PerlinNoise::default();
+        let point = Point::new(0.999_999, 0.5, 0.25);
+        let next = Point::new(1.000_001, 0.5, 0.25);
+        assert!((noise.noise(&point) - noise.noise(&next)).abs() < 0.0001);
+    }
+
+    #[test]
+    fn turbulence_accumulates_octaves() {
+        let noise = PerlinNoise::default();
+        let point = Point::new(0.3, 0.6, 0.2);
+        assert_eq!(noise.turbulence(&point, 0), 0.0);
+        assert_eq!(noise.turbulence(&point, 1), noise.noise(&point).abs());
+        assert!(noise.turbulence(&point, 4) >= noise.turbulence(&point, 1));
+    }
+}
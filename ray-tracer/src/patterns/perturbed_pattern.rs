@@ -0,0 +1,129 @@
+use crate::patterns::{Pattern, PerlinNoise};
+use crate::primitives::{Color, Point, Transformation, Vector};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Moves point by noise before passing it to inner pattern, which makes edges of regular
+/// patterns irregular
+#[derive(Clone, Debug)]
+pub struct PerturbedPattern {
+    pattern: Arc<dyn Pattern>,
+    noise: PerlinNoise,
+    /// Largest distance by which point can be moved in each axis
+    pub scale: f64,
+    transformation_inverse: Transformation,
+}
+
+impl PerturbedPattern {
+    /// Offsets added to point before sampling noise for y and z, so that all axes are moved by
+    /// uncorrelated values
+    const Y_OFFSET: Vector = Vector {
+        x: 31.416,
+        y: 0.0,
+        z: 0.0,
+    };
+    const Z_OFFSET: Vector = Vector {
+        x: 0.0,
+        y: 0.0,
+        z: 27.183,
+    };
+
+    pub fn new(pattern: Arc<dyn Pattern>, noise: PerlinNoise, scale: impl Into<f64>) -> Self {
+        return Self {
+            pattern,
+            noise,
+            scale: scale.into(),
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for PerturbedPattern {
+    /// Inner pattern is sampled in its own space, so its transformation is applied to moved point
+    fn color_at(&self, point: &Point) -> Color {
+        let offset = Vector::new(
+            self.noise.noise(point),
+            self.noise.noise(&(*point + Self::Y_OFFSET)),
+            self.noise.noise(&(*point + Self::Z_OFFSET)),
+        );
+        let moved = *point + offset * self.scale;
+        return self.pattern.color_at_nested(&moved);
+    }
+}
+
+impl Transform for PerturbedPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for PerturbedPattern {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.pattern.as_ref() == rhs.pattern.as_ref()
+                && self.noise == rhs.noise
+                && self.scale == rhs.scale
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for PerturbedPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("PerturbedPattern")
+            .field("pattern", &self.pattern)
+            .field("seed", &self.noise.seed())
+            .field("scale", &self.scale)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{StripePattern, TestPattern};
+    use crate::primitives::transformations;
+
+    #[test]
+    fn zero_scale_keeps_inner_pattern() {
+        let stripes: Arc<dyn Pattern> = Arc::new(StripePattern::new(Color::WHITE, Color::BLACK));
+        let pattern = PerturbedPattern::new(stripes.clone(), PerlinNoise::default(), 0);
+        for x in [0.2, 0.9, 1.1, 1.7] {
+            let point = Point::new(x, 0.3, 0.6);
+            assert_eq!(pattern.color_at(&point), stripes.color_at(&point));
+        }
+    }
+
+    #[test]
+    fn perturbed_point_stays_within_scale() {
+        let pattern = PerturbedPattern::new(Arc::new(TestPattern::new()), PerlinNoise::new(3), 0.5);
+        let point = Point::new(0.3, 1.6, -2.2);
+        let color = pattern.color_at(&point);
+        let moved = Point::new(color.red, color.green, color.blue);
+        assert_ne!(moved, point);
+        assert!((moved - point).magnitude() <= 0.5 * 3.0_f64.sqrt());
+    }
+
+    #[test]
+    fn inner_pattern_transformation_is_applied() {
+        let mut inner = TestPattern::new();
+        inner.set_transformation(transformations::scaling(2, 2, 2));
+        let pattern = PerturbedPattern::new(Arc::new(inner), PerlinNoise::default(), 0);
+        let color = pattern.color_at(&Point::new(2, 4, 6));
+        assert_eq!(color, Color::new(1, 2, 3));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use crate::utils::Squared;
+use core::fmt::{Display, Formatter, Result};
+
+/// Gradient from color a on y axis to color b at distance 1 from it, further points have color b
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradientPattern {
+    color_a: Color,
+    color_b: Color,
+    transformation_inverse: Transformation,
+}
+
+impl RadialGradientPattern {
+    pub const fn new(color_a: Color, color_b: Color) -> Self {
+        return Self {
+            color_a,
+            color_b,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for RadialGradientPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let fraction = (point.x.squared() + point.z.squared()).sqrt().min(1.0);
+        return self.color_a + (self.color_b - self.color_a) * fraction;
+    }
+}
+
+impl Transform for RadialGradientPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for RadialGradientPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("RadialGradientPattern")
+            .field("color_a", &self.color_a)
+            .field("color_b", &self.color_b)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Point::ORIGIN, Color::WHITE)]
+    #[case(Point::new(0, 5, 0), Color::WHITE)]
+    #[case(Point::new(0.25, 0, 0), Color::new(0.75, 0.75, 0.75))]
+    #[case(Point::new(0, 0, -0.5), Color::new(0.5, 0.5, 0.5))]
+    #[case(Point::new(0.6, 0, 0.8), Color::BLACK)]
+    #[case(Point::new(3, 0, 0), Color::BLACK)]
+    fn radial_gradient_grows_from_axis(#[case] point: Point, #[case] expected: Color) {
+        let pattern = RadialGradientPattern::new(Color::WHITE, Color::BLACK);
+        assert_eq!(pattern.color_at(&point), expected);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::patterns::Pattern;
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Single color everywhere, allows plain colors to be used where pattern is expected
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolidPattern {
+    color: Color,
+    transformation_inverse: Transformation,
+}
+
+impl SolidPattern {
+    pub const fn new(color: Color) -> Self {
+        return Self {
+            color,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for SolidPattern {
+    fn color_at(&self, _point: &Point) -> Color {
+        return self.color;
+    }
+}
+
+impl Transform for SolidPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for SolidPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("SolidPattern")
+            .field("color", &self.color)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solid_pattern_is_constant() {
+        let pattern = SolidPattern::new(Color::RED);
+        assert_eq!(pattern.color_at(&Point::ORIGIN), Color::RED);
+        assert_eq!(pattern.color_at(&Point::new(3.5, -2, 7)), Color::RED);
+    }
+}
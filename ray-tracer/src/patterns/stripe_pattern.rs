@@ -64,7 +64,7 @@ impl Display for StripePattern {
 mod tests {
     use super::*;
     use crate::composites::Material;
-    use crate::primitives::{Light, Vector, transformations};
+    use crate::primitives::{PointLight, Vector, transformations};
     use crate::shapes::{Sphere, Transform};
     use std::sync::Arc;
 
@@ -107,14 +107,14 @@ mod tests {
         material.specular = 0.0;
         let camera = Vector::new(0, 0, -1);
         let normal = Vector::new(0, 0, -1);
-        let light = Light::new(Point::new(0, 10, -10), Color::WHITE);
+        let light = PointLight::new(Point::new(0, 10, -10), Color::WHITE);
         let color1 = material.lighting(
             &shape,
             &light,
             &Point::new(0.9, 0, 0),
             &camera,
             &normal,
-            false,
+            Color::WHITE,
         );
         let color2 = material.lighting(
             &shape,
@@ -122,7 +122,7 @@ mod tests {
             &Point::new(1.1, 0, 0),
             &camera,
             &normal,
-            false,
+            Color::WHITE,
         );
         assert_eq!(color1, Color::WHITE);
         assert_eq!(color2, Color::BLACK);
@@ -133,7 +133,7 @@ mod tests {
         let mut sphere = Sphere::default();
         sphere.set_transformation(transformations::scaling(2, 2, 2));
         let pattern = StripePattern::new(Color::WHITE, Color::BLACK);
-        let color = pattern.color_at_shape(&sphere, &Point::new(1.5, 0, 0));
+        let color = pattern.color_at_shape(&sphere, &Point::new(1.5, 0, 0), 0.0);
         assert_eq!(color, Color::WHITE);
     }
 
@@ -142,7 +142,7 @@ mod tests {
         let sphere = Sphere::default();
         let mut pattern = StripePattern::new(Color::WHITE, Color::BLACK);
         pattern.set_transformation(transformations::scaling(2, 2, 2));
-        let color = pattern.color_at_shape(&sphere, &Point::new(1.5, 0, 0));
+        let color = pattern.color_at_shape(&sphere, &Point::new(1.5, 0, 0), 0.0);
         assert_eq!(color, Color::WHITE);
     }
 
@@ -152,7 +152,7 @@ mod tests {
         sphere.set_transformation(transformations::scaling(2, 2, 2));
         let mut pattern = StripePattern::new(Color::WHITE, Color::BLACK);
         pattern.set_transformation(transformations::translation(0.5, 0, 0));
-        let color = pattern.color_at_shape(&sphere, &Point::new(2.5, 0, 0));
+        let color = pattern.color_at_shape(&sphere, &Point::new(2.5, 0, 0), 0.0);
         assert_eq!(color, Color::WHITE);
     }
 }
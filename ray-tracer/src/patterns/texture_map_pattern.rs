@@ -0,0 +1,92 @@
+use crate::patterns::{Pattern, UvMapping, UvPattern};
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::Arc;
+
+/// Applies [UvPattern] to shape, points are turned into texture coordinates by [UvMapping]
+#[derive(Clone, Debug)]
+pub struct TextureMapPattern {
+    uv_pattern: Arc<dyn UvPattern>,
+    mapping: UvMapping,
+    transformation_inverse: Transformation,
+}
+
+impl TextureMapPattern {
+    pub const fn new(uv_pattern: Arc<dyn UvPattern>, mapping: UvMapping) -> Self {
+        return Self {
+            uv_pattern,
+            mapping,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for TextureMapPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let (u, v) = self.mapping.map(point);
+        return self.uv_pattern.uv_color_at(u, v);
+    }
+}
+
+impl Transform for TextureMapPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl PartialEq for TextureMapPattern {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.uv_pattern.as_ref() == rhs.uv_pattern.as_ref()
+                && self.mapping == rhs.mapping
+                && self.transformation_inverse == rhs.transformation_inverse;
+    }
+}
+
+impl Display for TextureMapPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("TextureMapPattern")
+            .field("uv_pattern", &self.uv_pattern)
+            .field("mapping", &self.mapping)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::UvCheckerPattern;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Point::new(0.4315, 0.467, 0.7719), Color::WHITE)]
+    #[case(Point::new(-0.9654, 0.2552, -0.0534), Color::BLACK)]
+    #[case(Point::new(0.1039, 0.709, 0.6975), Color::WHITE)]
+    #[case(Point::new(-0.4986, -0.7856, -0.3663), Color::BLACK)]
+    #[case(Point::new(-0.0317, -0.9395, 0.3411), Color::BLACK)]
+    #[case(Point::new(0.4809, -0.7721, 0.4154), Color::BLACK)]
+    #[case(Point::new(0.0285, -0.9612, -0.2745), Color::BLACK)]
+    #[case(Point::new(-0.5734, -0.2162, -0.7903), Color::WHITE)]
+    #[case(Point::new(0.7688, -0.147, 0.6223), Color::BLACK)]
+    #[case(Point::new(-0.7652, 0.2175, 0.606), Color::BLACK)]
+    fn checker_pattern_mapped_onto_sphere(#[case] point: Point, #[case] expected: Color) {
+        let checkers = UvCheckerPattern::new(16, 8, Color::BLACK, Color::WHITE);
+        let pattern = TextureMapPattern::new(Arc::new(checkers), UvMapping::Spherical);
+        assert_eq!(pattern.color_at(&point), expected);
+    }
+}
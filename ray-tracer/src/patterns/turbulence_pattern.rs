@@ -0,0 +1,85 @@
+use crate::patterns::{Pattern, PerlinNoise};
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Blend of two colors driven by turbulence, which gives cloudy look
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurbulencePattern {
+    color_a: Color,
+    color_b: Color,
+    noise: PerlinNoise,
+    pub octaves: u32,
+    transformation_inverse: Transformation,
+}
+
+impl TurbulencePattern {
+    pub fn new(color_a: Color, color_b: Color, noise: PerlinNoise) -> Self {
+        return Self {
+            color_a,
+            color_b,
+            noise,
+            octaves: 4,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for TurbulencePattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let fraction = self.noise.turbulence(point, self.octaves).min(1.0);
+        return self.color_a + (self.color_b - self.color_a) * fraction;
+    }
+}
+
+impl Transform for TurbulencePattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for TurbulencePattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("TurbulencePattern")
+            .field("color_a", &self.color_a)
+            .field("color_b", &self.color_b)
+            .field("seed", &self.noise.seed())
+            .field("octaves", &self.octaves)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turbulence_pattern_at_lattice_point() {
+        let pattern = TurbulencePattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+        assert_eq!(pattern.color_at(&Point::new(1, 2, 3)), Color::BLACK);
+    }
+
+    #[test]
+    fn turbulence_pattern_varies_between_lattice_points() {
+        let pattern = TurbulencePattern::new(Color::BLACK, Color::RED, PerlinNoise::new(5));
+        let color_1 = pattern.color_at(&Point::new(0.3, 0.2, 0.9));
+        let color_2 = pattern.color_at(&Point::new(0.7, 0.5, 0.1));
+        assert_ne!(color_1, color_2);
+        assert!(color_1.red > 0.0 && color_1.red <= 1.0);
+        assert_eq!(color_1.green, 0.0);
+    }
+}
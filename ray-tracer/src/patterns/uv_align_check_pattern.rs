@@ -0,0 +1,93 @@
+use crate::patterns::UvPattern;
+use crate::primitives::Color;
+use core::fmt::{Display, Formatter, Result};
+
+/// Single color with differently colored corners, useful for checking orientation of texture
+/// mapped onto shape
+#[derive(Clone, Debug, PartialEq)]
+pub struct UvAlignCheckPattern {
+    main: Color,
+    upper_left: Color,
+    upper_right: Color,
+    bottom_left: Color,
+    bottom_right: Color,
+}
+
+impl UvAlignCheckPattern {
+    pub const CORNER_SIZE: f64 = 0.2;
+
+    pub const fn new(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> Self {
+        return Self {
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        };
+    }
+}
+
+impl UvPattern for UvAlignCheckPattern {
+    fn uv_color_at(&self, u: f64, v: f64) -> Color {
+        let is_left = u < Self::CORNER_SIZE;
+        let is_right = u > 1.0 - Self::CORNER_SIZE;
+        if v > 1.0 - Self::CORNER_SIZE {
+            if is_left {
+                return self.upper_left;
+            }
+            if is_right {
+                return self.upper_right;
+            }
+        } else if v < Self::CORNER_SIZE {
+            if is_left {
+                return self.bottom_left;
+            }
+            if is_right {
+                return self.bottom_right;
+            }
+        }
+        return self.main;
+    }
+}
+
+impl Display for UvAlignCheckPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("UvAlignCheckPattern")
+            .field("main", &self.main)
+            .field("upper_left", &self.upper_left)
+            .field("upper_right", &self.upper_right)
+            .field("bottom_left", &self.bottom_left)
+            .field("bottom_right", &self.bottom_right)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.5, 0.5, Color::WHITE)]
+    #[case(0.1, 0.9, Color::new(1, 0, 0))]
+    #[case(0.9, 0.9, Color::new(1, 1, 0))]
+    #[case(0.1, 0.1, Color::new(0, 1, 0))]
+    #[case(0.9, 0.1, Color::new(0, 1, 1))]
+    fn layout_of_align_check_pattern(#[case] u: f64, #[case] v: f64, #[case] expected: Color) {
+        let pattern = UvAlignCheckPattern::new(
+            Color::WHITE,
+            Color::new(1, 0, 0),
+            Color::new(1, 1, 0),
+            Color::new(0, 1, 0),
+            Color::new(0, 1, 1),
+        );
+        assert_eq!(pattern.uv_color_at(u, v), expected);
+    }
+}
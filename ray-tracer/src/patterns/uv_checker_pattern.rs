@@ -0,0 +1,69 @@
+use crate::patterns::UvPattern;
+use crate::primitives::Color;
+use core::fmt::{Display, Formatter, Result};
+
+/// Checkers dividing unit square into given number of squares along each texture coordinate
+#[derive(Clone, Debug, PartialEq)]
+pub struct UvCheckerPattern {
+    width: f64,
+    height: f64,
+    color_a: Color,
+    color_b: Color,
+}
+
+impl UvCheckerPattern {
+    pub fn new(
+        width: impl Into<f64>,
+        height: impl Into<f64>,
+        color_a: Color,
+        color_b: Color,
+    ) -> Self {
+        return Self {
+            width: width.into(),
+            height: height.into(),
+            color_a,
+            color_b,
+        };
+    }
+}
+
+impl UvPattern for UvCheckerPattern {
+    fn uv_color_at(&self, u: f64, v: f64) -> Color {
+        let u = (u * self.width).floor() as i64;
+        let v = (v * self.height).floor() as i64;
+        return if (u + v) % 2 == 0 {
+            self.color_a
+        } else {
+            self.color_b
+        };
+    }
+}
+
+impl Display for UvCheckerPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("UvCheckerPattern")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("color_a", &self.color_a)
+            .field("color_b", &self.color_b)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0.0, 0.0, Color::BLACK)]
+    #[case(0.5, 0.0, Color::WHITE)]
+    #[case(0.0, 0.5, Color::WHITE)]
+    #[case(0.5, 0.5, Color::BLACK)]
+    #[case(1.0, 1.0, Color::BLACK)]
+    fn checker_pattern_in_2d(#[case] u: f64, #[case] v: f64, #[case] expected: Color) {
+        let pattern = UvCheckerPattern::new(2, 2, Color::BLACK, Color::WHITE);
+        assert_eq!(pattern.uv_color_at(u, v), expected);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::composites::Canvas;
+use crate::patterns::UvPattern;
+use crate::primitives::Color;
+use core::fmt::{Display, Formatter, Result};
+
+/// Handling of texture coordinates falling outside of image
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Image repeats, pixels on opposite edges are blended with each other
+    #[default]
+    Wrap,
+    /// Pixels on edges of image are stretched outwards
+    Clamp,
+}
+
+impl WrapMode {
+    fn resolve(&self, coordinate: i64, size: u32) -> u32 {
+        let size = i64::from(size);
+        return match self {
+            Self::Wrap => coordinate.rem_euclid(size) as u32,
+            Self::Clamp => coordinate.clamp(0, size - 1) as u32,
+        };
+    }
+}
+
+impl Display for WrapMode {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let name = match self {
+            Self::Wrap => "wrap",
+            Self::Clamp => "clamp",
+        };
+        return formatter.write_str(name);
+    }
+}
+
+/// Image used as texture, colors between centers of pixels are interpolated bilinearly
+#[derive(Clone, Debug, PartialEq)]
+pub struct UvImagePattern {
+    canvas: Canvas,
+    pub wrap_mode: WrapMode,
+}
+
+impl UvImagePattern {
+    pub fn new(canvas: Canvas) -> Self {
+        return Self {
+            canvas,
+            wrap_mode: WrapMode::default(),
+        };
+    }
+
+    fn pixel(&self, x: i64, y: i64) -> Color {
+        let x = self.wrap_mode.resolve(x, self.canvas.width);
+        let y = self.wrap_mode.resolve(y, self.canvas.height);
+        return *self.canvas.get_pixel(x, y);
+    }
+}
+
+impl UvPattern for UvImagePattern {
+    /// Texture coordinate v grows upwards while rows of image are stored from the top
+    fn uv_color_at(&self, u: f64, v: f64) -> Color {
+        if self.canvas.is_empty() {
+            return Color::BLACK;
+        }
+        let x = u * f64::from(self.canvas.width) - 0.5;
+        let y = (1.0 - v) * f64::from(self.canvas.height) - 0.5;
+        let (left, top) = (x.floor(), y.floor());
+        let (weight_x, weight_y) = (x - left, y - top);
+        let (left, top) = (left as i64, top as i64);
+
+        let upper = self.pixel(left, top) * (1.0 - weight_x) + self.pixel(left + 1, top) * weight_x;
+        let lower =
+            self.pixel(left, top + 1) * (1.0 - weight_x) + self.pixel(left + 1, top + 1) * weight_x;
+        return upper * (1.0 - weight_y) + lower * weight_y;
+    }
+}
+
+impl Display for UvImagePattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("UvImagePattern")
+            .field("width", &self.canvas.width)
+            .field("height", &self.canvas.height)
+            .field("wrap_mode", &self.wrap_mode)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+    use rstest::rstest;
+
+    /// Returns 2x2 image with white top left, black bottom right and red other pixels
+    fn image_pattern(wrap_mode: WrapMode) -> UvImagePattern {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_pixel(0, 0, Color::WHITE);
+        canvas.set_pixel(1, 0, Color::RED);
+        canvas.set_pixel(0, 1, Color::RED);
+        let mut pattern = UvImagePattern::new(canvas);
+        pattern.wrap_mode = wrap_mode;
+        return pattern;
+    }
+
+    #[rstest]
+    #[case(0.25, 0.75, Color::WHITE)]
+    #[case(0.75, 0.75, Color::RED)]
+    #[case(0.25, 0.25, Color::RED)]
+    #[case(0.75, 0.25, Color::BLACK)]
+    fn centers_of_pixels_keep_their_colors(
+        #[case] u: f64,
+        #[case] v: f64,
+        #[case] expected: Color,
+    ) {
+        let pattern = image_pattern(WrapMode::Wrap);
+        assert!(pattern.uv_color_at(u, v).coarse_eq(&expected));
+    }
+
+    #[test]
+    fn colors_between_pixels_are_interpolated() {
+        let pattern = image_pattern(WrapMode::Clamp);
+        assert!(
+            pattern
+                .uv_color_at(0.5, 0.75)
+                .coarse_eq(&Color::new(1, 0.5, 0.5))
+        );
+        assert!(
+            pattern
+                .uv_color_at(0.5, 0.5)
+                .coarse_eq(&Color::new(0.75, 0.25, 0.25))
+        );
+    }
+
+    #[test]
+    fn wrapping_and_clamping_at_edges() {
+        let wrapped = image_pattern(WrapMode::Wrap);
+        let clamped = image_pattern(WrapMode::Clamp);
+        assert!(
+            wrapped
+                .uv_color_at(0.0, 0.75)
+                .coarse_eq(&Color::new(1, 0.5, 0.5))
+        );
+        assert!(clamped.uv_color_at(0.0, 0.75).coarse_eq(&Color::WHITE));
+        assert!(
+            wrapped
+                .uv_color_at(1.25, 0.75)
+                .coarse_eq(&wrapped.uv_color_at(0.25, 0.75))
+        );
+        assert!(clamped.uv_color_at(1.25, 0.75).coarse_eq(&Color::RED));
+    }
+}
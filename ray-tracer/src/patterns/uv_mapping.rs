@@ -0,0 +1,191 @@
+use crate::consts::PI;
+use crate::primitives::Point;
+use core::fmt::{Display, Formatter, Result};
+
+/// Projection of point in pattern space onto texture coordinates (u, v) in range [0, 1]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UvMapping {
+    /// Longitude and latitude of point on sphere centered at origin
+    #[default]
+    Spherical,
+    /// Fractional parts of x and z coordinates, repeats on every unit square of xz plane
+    Planar,
+    /// Angle around y axis and fractional part of y coordinate
+    Cylindrical,
+    /// Coordinates on face of cube from -1 to 1 which point belongs to, all faces share single
+    /// texture
+    Cube,
+}
+
+/// Face of cube from -1 to 1, named as seen from outside of cube
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubeFace {
+    Left,
+    Front,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: &Point) -> (f64, f64) {
+        return match self {
+            Self::Spherical => {
+                let theta = point.x.atan2(point.z);
+                let radius = (point.x * point.x + point.y * point.y + point.z * point.z).sqrt();
+                let phi = (point.y / radius).acos();
+                let raw_u = theta / (2.0 * PI);
+                (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+            }
+            Self::Planar => (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0)),
+            Self::Cylindrical => {
+                let theta = point.x.atan2(point.z);
+                let raw_u = theta / (2.0 * PI);
+                (1.0 - (raw_u + 0.5), point.y.rem_euclid(1.0))
+            }
+            Self::Cube => CubeFace::of(point).map(point),
+        };
+    }
+}
+
+impl CubeFace {
+    pub const ALL: [Self; 6] = [
+        Self::Left,
+        Self::Front,
+        Self::Right,
+        Self::Back,
+        Self::Up,
+        Self::Down,
+    ];
+
+    /// Returns face pointed to by the largest coordinate of point
+    pub fn of(point: &Point) -> Self {
+        let coordinate = point.x.abs().max(point.y.abs()).max(point.z.abs());
+        return if coordinate == point.x {
+            Self::Right
+        } else if coordinate == -point.x {
+            Self::Left
+        } else if coordinate == point.y {
+            Self::Up
+        } else if coordinate == -point.y {
+            Self::Down
+        } else if coordinate == point.z {
+            Self::Front
+        } else {
+            Self::Back
+        };
+    }
+
+    /// Returns texture coordinates of point on this face
+    pub fn map(&self, point: &Point) -> (f64, f64) {
+        let unit = |value: f64| value.rem_euclid(2.0) / 2.0;
+        return match self {
+            Self::Left => (unit(point.z + 1.0), unit(point.y + 1.0)),
+            Self::Front => (unit(point.x + 1.0), unit(point.y + 1.0)),
+            Self::Right => (unit(1.0 - point.z), unit(point.y + 1.0)),
+            Self::Back => (unit(1.0 - point.x), unit(point.y + 1.0)),
+            Self::Up => (unit(point.x + 1.0), unit(1.0 - point.z)),
+            Self::Down => (unit(point.x + 1.0), unit(point.z + 1.0)),
+        };
+    }
+}
+
+impl Display for UvMapping {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        let name = match self {
+            Self::Spherical => "spherical",
+            Self::Planar => "planar",
+            Self::Cylindrical => "cylindrical",
+            Self::Cube => "cube",
+        };
+        return formatter.write_str(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+    use rstest::rstest;
+
+    fn assert_uv(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            actual.0.coarse_eq(&expected.0),
+            "{actual:?} != {expected:?}"
+        );
+        assert!(
+            actual.1.coarse_eq(&expected.1),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0, -1), (0.0, 0.5))]
+    #[case(Point::new(1, 0, 0), (0.25, 0.5))]
+    #[case(Point::new(0, 0, 1), (0.5, 0.5))]
+    #[case(Point::new(-1, 0, 0), (0.75, 0.5))]
+    #[case(Point::new(0, 1, 0), (0.5, 1.0))]
+    #[case(Point::new(0, -1, 0), (0.5, 0.0))]
+    #[case(Point::new(2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0), (0.25, 0.75))]
+    fn spherical_mapping(#[case] point: Point, #[case] expected: (f64, f64)) {
+        assert_uv(UvMapping::Spherical.map(&point), expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0.25, 0, 0.5), (0.25, 0.5))]
+    #[case(Point::new(0.25, 0, -0.25), (0.25, 0.75))]
+    #[case(Point::new(0.25, 0.5, -0.25), (0.25, 0.75))]
+    #[case(Point::new(1.25, 0, 0.5), (0.25, 0.5))]
+    #[case(Point::new(0.25, 0, -1.75), (0.25, 0.25))]
+    #[case(Point::new(1, 0, -1), (0.0, 0.0))]
+    #[case(Point::new(0, 0, 0), (0.0, 0.0))]
+    fn planar_mapping(#[case] point: Point, #[case] expected: (f64, f64)) {
+        assert_uv(UvMapping::Planar.map(&point), expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0, -1), (0.0, 0.0))]
+    #[case(Point::new(0, 0.5, -1), (0.0, 0.5))]
+    #[case(Point::new(0, 1, -1), (0.0, 0.0))]
+    #[case(Point::new(2.0_f64.sqrt() / 2.0, 0.5, -(2.0_f64.sqrt()) / 2.0), (0.125, 0.5))]
+    #[case(Point::new(1, 0.5, 0), (0.25, 0.5))]
+    #[case(Point::new(0, -0.25, 1), (0.5, 0.75))]
+    #[case(Point::new(-1, 1.25, 0), (0.75, 0.25))]
+    fn cylindrical_mapping(#[case] point: Point, #[case] expected: (f64, f64)) {
+        assert_uv(UvMapping::Cylindrical.map(&point), expected);
+    }
+
+    #[rstest]
+    #[case(Point::new(-1, 0.5, -0.25), CubeFace::Left)]
+    #[case(Point::new(1.1, -0.75, 0.8), CubeFace::Right)]
+    #[case(Point::new(0.1, 0.6, 0.9), CubeFace::Front)]
+    #[case(Point::new(-0.7, 0, -2), CubeFace::Back)]
+    #[case(Point::new(0.5, 1, 0.9), CubeFace::Up)]
+    #[case(Point::new(-0.2, -1.3, 1.1), CubeFace::Down)]
+    fn face_of_cube_from_point(#[case] point: Point, #[case] expected: CubeFace) {
+        assert_eq!(CubeFace::of(&point), expected);
+    }
+
+    #[rstest]
+    #[case(CubeFace::Front, Point::new(-0.5, 0.5, 1), (0.25, 0.75))]
+    #[case(CubeFace::Front, Point::new(0.5, -0.5, 1), (0.75, 0.25))]
+    #[case(CubeFace::Back, Point::new(0.5, 0.5, -1), (0.25, 0.75))]
+    #[case(CubeFace::Back, Point::new(-0.5, -0.5, -1), (0.75, 0.25))]
+    #[case(CubeFace::Left, Point::new(-1, 0.5, -0.5), (0.25, 0.75))]
+    #[case(CubeFace::Left, Point::new(-1, -0.5, 0.5), (0.75, 0.25))]
+    #[case(CubeFace::Right, Point::new(1, 0.5, 0.5), (0.25, 0.75))]
+    #[case(CubeFace::Right, Point::new(1, -0.5, -0.5), (0.75, 0.25))]
+    #[case(CubeFace::Up, Point::new(-0.5, 1, -0.5), (0.25, 0.75))]
+    #[case(CubeFace::Up, Point::new(0.5, 1, 0.5), (0.75, 0.25))]
+    #[case(CubeFace::Down, Point::new(-0.5, -1, 0.5), (0.25, 0.75))]
+    #[case(CubeFace::Down, Point::new(0.5, -1, -0.5), (0.75, 0.25))]
+    fn uv_mapping_of_cube_face(
+        #[case] face: CubeFace,
+        #[case] point: Point,
+        #[case] expected: (f64, f64),
+    ) {
+        assert_uv(face.map(&point), expected);
+        assert_uv(UvMapping::Cube.map(&point), expected);
+    }
+}
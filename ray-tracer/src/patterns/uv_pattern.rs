@@ -0,0 +1,16 @@
+use crate::dyn_partial_eq::DynPartialEq;
+use crate::primitives::Color;
+use core::fmt::{Debug, Display};
+
+/// Two dimensional pattern defined on unit square, it is applied to shapes through
+/// [TextureMapPattern](super::TextureMapPattern) or [CubeMapPattern](super::CubeMapPattern)
+pub trait UvPattern: Debug + Display + Send + Sync + DynPartialEq {
+    /// Returns color at given texture coordinates, both of them are expected in range [0, 1]
+    fn uv_color_at(&self, u: f64, v: f64) -> Color;
+}
+
+impl PartialEq for dyn UvPattern {
+    fn eq(&self, other: &Self) -> bool {
+        return self.dyn_eq(DynPartialEq::as_any(other));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::patterns::{Pattern, PerlinNoise};
+use crate::primitives::{Color, Point, Transformation};
+use crate::shapes::Transform;
+use core::fmt::{Display, Formatter, Result};
+
+/// Concentric rings around y axis like in cross section of tree trunk, rings are warped by noise
+#[derive(Clone, Debug, PartialEq)]
+pub struct WoodPattern {
+    color_a: Color,
+    color_b: Color,
+    noise: PerlinNoise,
+    /// How far noise moves rings, 0 gives perfect circles
+    pub distortion: f64,
+    transformation_inverse: Transformation,
+}
+
+impl WoodPattern {
+    pub fn new(color_a: Color, color_b: Color, noise: PerlinNoise) -> Self {
+        return Self {
+            color_a,
+            color_b,
+            noise,
+            distortion: 0.1,
+            transformation_inverse: Transformation::IDENTITY,
+        };
+    }
+}
+
+impl Pattern for WoodPattern {
+    fn color_at(&self, point: &Point) -> Color {
+        let radius = point.x.hypot(point.z);
+        let fraction = self
+            .distortion
+            .mul_add(self.noise.noise(point), radius)
+            .rem_euclid(1.0);
+        return self.color_a + (self.color_b - self.color_a) * fraction;
+    }
+}
+
+impl Transform for WoodPattern {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+}
+
+impl Display for WoodPattern {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("WoodPattern")
+            .field("color_a", &self.color_a)
+            .field("color_b", &self.color_b)
+            .field("seed", &self.noise.seed())
+            .field("distortion", &self.distortion)
+            .field("transformation", &self.transformation())
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CoarseEq;
+
+    #[test]
+    fn undistorted_wood_forms_rings() {
+        let mut pattern = WoodPattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+        pattern.distortion = 0.0;
+        assert_eq!(pattern.color_at(&Point::ORIGIN), Color::BLACK);
+        assert!(
+            pattern
+                .color_at(&Point::new(0, 5, 0.5))
+                .coarse_eq(&Color::new(0.5, 0.5, 0.5))
+        );
+        assert!(
+            pattern
+                .color_at(&Point::new(1.25, 0, 0))
+                .coarse_eq(&Color::new(0.25, 0.25, 0.25))
+        );
+    }
+
+    #[test]
+    fn noise_warps_wood_rings() {
+        let straight = {
+            let mut pattern = WoodPattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+            pattern.distortion = 0.0;
+            pattern
+        };
+        let warped = WoodPattern::new(Color::BLACK, Color::WHITE, PerlinNoise::default());
+        let point = Point::new(0.3, 0.7, 0.45);
+        assert_ne!(warped.color_at(&point), straight.color_at(&point));
+    }
+}
@@ -1,15 +1,25 @@
 //! Collection of basic types and methods useful for modelling world
 
+pub use area_light::AreaLight;
+pub use attenuation::Attenuation;
 pub use color::Color;
-pub use light::Light;
+pub use directional_light::DirectionalLight;
+pub use light::{Light, LightSample};
 pub use matrix::Matrix;
 pub use point::Point;
+pub use point_light::PointLight;
+pub use spot_light::SpotLight;
 pub use transformations::Transformation;
 pub use vector::Vector;
 
+mod area_light;
+mod attenuation;
 mod color;
+mod directional_light;
 mod light;
 mod matrix;
 mod point;
+mod point_light;
+mod spot_light;
 pub mod transformations;
 mod vector;
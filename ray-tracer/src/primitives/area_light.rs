@@ -0,0 +1,178 @@
+use crate::primitives::{Attenuation, Color, Light, LightSample, Point, Vector};
+use crate::utils::{CoarseEq, Random};
+use core::fmt::{Display, Formatter, Result};
+
+/// Rectangular light spanned by two edges starting at its corner, the rectangle is divided into
+/// cells and each cell is sampled once which produces soft shadows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AreaLight {
+    pub corner: Point,
+    pub u_vector: Vector,
+    u_steps: usize,
+    pub v_vector: Vector,
+    v_steps: usize,
+    pub intensity: Color,
+    /// Whether samples are placed randomly within their cells instead of at their centers
+    pub jitter: bool,
+    pub attenuation: Attenuation,
+}
+
+impl AreaLight {
+    pub fn new(
+        corner: Point,
+        u_vector: Vector,
+        u_steps: usize,
+        v_vector: Vector,
+        v_steps: usize,
+        intensity: Color,
+    ) -> Self {
+        return Self {
+            corner,
+            u_vector,
+            u_steps: u_steps.max(1),
+            v_vector,
+            v_steps: v_steps.max(1),
+            intensity,
+            jitter: true,
+            attenuation: Attenuation::NONE,
+        };
+    }
+
+    pub fn u_steps(&self) -> usize {
+        return self.u_steps;
+    }
+
+    pub fn v_steps(&self) -> usize {
+        return self.v_steps;
+    }
+
+    pub fn center(&self) -> Point {
+        return self.corner + self.u_vector / 2.0 + self.v_vector / 2.0;
+    }
+
+    /// Returns point within cell with given coordinates, offsets are relative to cell size
+    pub fn point_in_cell(&self, u: usize, v: usize, u_offset: f64, v_offset: f64) -> Point {
+        return self.corner
+            + self.u_vector * ((u as f64 + u_offset) / self.u_steps as f64)
+            + self.v_vector * ((v as f64 + v_offset) / self.v_steps as f64);
+    }
+
+    /// Returns position of sample with given index, jittered samples are spread differently for
+    /// each lit point
+    pub fn sample_position(&self, point: &Point, index: usize) -> Point {
+        let (u, v) = (index % self.u_steps, index / self.u_steps);
+        if !self.jitter {
+            return self.point_in_cell(u, v, 0.5, 0.5);
+        }
+        let mut random = Random::from_point(point, index as u64);
+        return self.point_in_cell(u, v, random.next_f64(), random.next_f64());
+    }
+}
+
+impl Light for AreaLight {
+    fn intensity(&self) -> Color {
+        return self.intensity;
+    }
+
+    fn ambient_intensity(&self, point: &Point) -> Color {
+        let distance = (self.center() - *point).magnitude();
+        return self.intensity * self.attenuation.factor(distance);
+    }
+
+    fn sample_count(&self) -> usize {
+        return self.u_steps * self.v_steps;
+    }
+
+    fn sample(&self, point: &Point, index: usize) -> LightSample {
+        let position = self.sample_position(point, index);
+        return LightSample::towards(point, &position, self.intensity)
+            .attenuated(&self.attenuation);
+    }
+}
+
+impl CoarseEq for AreaLight {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.corner.coarse_eq(&rhs.corner)
+                && self.u_vector.coarse_eq(&rhs.u_vector)
+                && self.u_steps == rhs.u_steps
+                && self.v_vector.coarse_eq(&rhs.v_vector)
+                && self.v_steps == rhs.v_steps
+                && self.intensity.coarse_eq(&rhs.intensity)
+                && self.jitter == rhs.jitter
+                && self.attenuation.coarse_eq(&rhs.attenuation);
+    }
+}
+
+impl Display for AreaLight {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("AreaLight")
+            .field("corner", &self.corner)
+            .field("u_vector", &self.u_vector)
+            .field("u_steps", &self.u_steps)
+            .field("v_vector", &self.v_vector)
+            .field("v_steps", &self.v_steps)
+            .field("intensity", &self.intensity)
+            .field("jitter", &self.jitter)
+            .field("attenuation", &self.attenuation)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn default_area_light() -> AreaLight {
+        let mut light = AreaLight::new(
+            Point::ORIGIN,
+            Vector::new(2, 0, 0),
+            4,
+            Vector::new(0, 0, 1),
+            2,
+            Color::WHITE,
+        );
+        light.jitter = false;
+        return light;
+    }
+
+    #[test]
+    fn creating_area_light() {
+        let light = default_area_light();
+        assert_eq!(light.corner, Point::ORIGIN);
+        assert_eq!(light.sample_count(), 8);
+        assert_eq!(light.center(), Point::new(1, 0, 0.5));
+    }
+
+    #[rstest]
+    #[case(0, 0, Point::new(0.25, 0, 0.25))]
+    #[case(1, 0, Point::new(0.75, 0, 0.25))]
+    #[case(0, 1, Point::new(0.25, 0, 0.75))]
+    #[case(2, 0, Point::new(1.25, 0, 0.25))]
+    #[case(3, 1, Point::new(1.75, 0, 0.75))]
+    fn finding_single_point_on_area_light(
+        #[case] u: usize,
+        #[case] v: usize,
+        #[case] expected: Point,
+    ) {
+        let light = default_area_light();
+        assert_eq!(light.point_in_cell(u, v, 0.5, 0.5), expected);
+        assert_eq!(light.sample_position(&Point::ORIGIN, v * 4 + u), expected);
+    }
+
+    #[test]
+    fn jittered_samples_stay_within_their_cells() {
+        let mut light = default_area_light();
+        light.jitter = true;
+        let point = Point::new(3, -1, 2);
+        for index in 0..light.sample_count() {
+            let sample = light.sample_position(&point, index);
+            let (u, v) = ((index % 4) as f64, (index / 4) as f64);
+            assert!((u * 0.5..(u + 1.0) * 0.5).contains(&sample.x));
+            assert!((v * 0.5..(v + 1.0) * 0.5).contains(&sample.z));
+            assert_eq!(sample, light.sample_position(&point, index));
+        }
+    }
+}
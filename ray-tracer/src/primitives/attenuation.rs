@@ -0,0 +1,109 @@
+use crate::utils::{CoarseEq, Squared};
+use core::fmt::{Display, Formatter, Result};
+
+/// Falloff of light intensity with distance, intensity is divided by
+/// `constant + linear * distance + quadratic * distance^2`, coefficients are non-negative and
+/// not all zero so that intensity only decreases with distance
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attenuation {
+    pub constant: f64,
+    pub linear: f64,
+    pub quadratic: f64,
+}
+
+impl Attenuation {
+    /// Intensity does not change with distance
+    pub const NONE: Self = Self::new(1.0, 0.0, 0.0);
+
+    /// Physically based falloff, intensity is inversely proportional to squared distance
+    pub const INVERSE_SQUARE: Self = Self::new(0.0, 0.0, 1.0);
+
+    pub const fn new(constant: f64, linear: f64, quadratic: f64) -> Self {
+        assert!(
+            Self::are_valid_coefficients(constant, linear, quadratic),
+            "Attenuation coefficients must be non-negative and not all zero"
+        );
+        return Self {
+            constant,
+            linear,
+            quadratic,
+        };
+    }
+
+    pub const fn are_valid_coefficients(constant: f64, linear: f64, quadratic: f64) -> bool {
+        return constant >= 0.0
+            && linear >= 0.0
+            && quadratic >= 0.0
+            && constant + linear + quadratic > 0.0;
+    }
+
+    /// Returns fraction of intensity remaining at given distance from light, divisor is zero
+    /// only for point at position of light without constant term which is then fully lit
+    pub fn factor(&self, distance: f64) -> f64 {
+        let divisor = self.constant + self.linear * distance + self.quadratic * distance.squared();
+        if divisor == 0.0 {
+            return 1.0;
+        }
+        return 1.0 / divisor;
+    }
+}
+
+impl Default for Attenuation {
+    fn default() -> Self {
+        return Self::NONE;
+    }
+}
+
+impl CoarseEq for Attenuation {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.constant.coarse_eq(&rhs.constant)
+                && self.linear.coarse_eq(&rhs.linear)
+                && self.quadratic.coarse_eq(&rhs.quadratic);
+    }
+}
+
+impl Display for Attenuation {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("Attenuation")
+            .field("constant", &self.constant)
+            .field("linear", &self.linear)
+            .field("quadratic", &self.quadratic)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Attenuation::NONE, 0.0, 1.0)]
+    #[case(Attenuation::NONE, 100.0, 1.0)]
+    #[case(Attenuation::INVERSE_SQUARE, 2.0, 0.25)]
+    #[case(Attenuation::INVERSE_SQUARE, 0.0, 1.0)]
+    #[case(Attenuation::new(1.0, 0.5, 0.0), 2.0, 0.5)]
+    #[case(Attenuation::new(1.0, 1.0, 2.0), 1.0, 0.25)]
+    fn attenuation_factor(
+        #[case] attenuation: Attenuation,
+        #[case] distance: f64,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(attenuation.factor(distance), expected);
+    }
+
+    #[rstest]
+    #[case(1.0, -0.5, 0.0)]
+    #[case(-1.0, 0.0, 1.0)]
+    #[case(0.0, 0.0, 0.0)]
+    #[should_panic(expected = "Attenuation coefficients must be non-negative and not all zero")]
+    fn invalid_attenuation_coefficients(
+        #[case] constant: f64,
+        #[case] linear: f64,
+        #[case] quadratic: f64,
+    ) {
+        Attenuation::new(constant, linear, quadratic);
+    }
+}
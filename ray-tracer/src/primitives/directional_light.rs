@@ -0,0 +1,79 @@
+use crate::primitives::{Color, Light, LightSample, Point, Vector};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+/// Light infinitely far away, like the sun, all its rays are parallel and its intensity does not
+/// fall off with distance
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionalLight {
+    /// Direction in which light travels
+    pub direction: Vector,
+    pub intensity: Color,
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Vector, intensity: Color) -> Self {
+        return Self {
+            direction: direction.normalized(),
+            intensity,
+        };
+    }
+}
+
+impl Light for DirectionalLight {
+    fn intensity(&self) -> Color {
+        return self.intensity;
+    }
+
+    fn sample_count(&self) -> usize {
+        return 1;
+    }
+
+    fn sample(&self, _point: &Point, _index: usize) -> LightSample {
+        return LightSample {
+            direction: -self.direction,
+            distance: f64::INFINITY,
+            intensity: self.intensity,
+        };
+    }
+}
+
+impl CoarseEq for DirectionalLight {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.direction.coarse_eq(&rhs.direction)
+                && self.intensity.coarse_eq(&rhs.intensity);
+    }
+}
+
+impl Display for DirectionalLight {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("DirectionalLight")
+            .field("direction", &self.direction)
+            .field("intensity", &self.intensity)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directional_light_is_normalized() {
+        let light = DirectionalLight::new(Vector::new(0, -2, 0), Color::WHITE);
+        assert_eq!(light.direction, Vector::DOWN);
+    }
+
+    #[test]
+    fn directional_light_sample_is_same_everywhere() {
+        let light = DirectionalLight::new(Vector::DOWN, Color::WHITE);
+        let sample_1 = light.sample(&Point::ORIGIN, 0);
+        let sample_2 = light.sample(&Point::new(100, -50, 3), 0);
+        assert_eq!(sample_1, sample_2);
+        assert_eq!(sample_1.direction, Vector::UP);
+        assert_eq!(sample_1.distance, f64::INFINITY);
+        assert_eq!(sample_1.intensity, Color::WHITE);
+    }
+}
@@ -1,65 +1,101 @@
-use crate::primitives::{Color, Point};
-use crate::utils::CoarseEq;
-use core::fmt::{Display, Formatter, Result};
+use crate::dyn_partial_eq::DynPartialEq;
+use crate::primitives::{Attenuation, Color, Point, Vector};
+use core::fmt::Debug;
 
+/// Light arriving at point from single sample of [Light]
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Light {
-    pub position: Point,
+pub struct LightSample {
+    /// Normalized direction from lit point towards light
+    pub direction: Vector,
+    /// Distance to light, infinite for lights without position
+    pub distance: f64,
+    /// Intensity of light reaching the point, already reduced by any falloff
     pub intensity: Color,
 }
 
-impl Light {
-    /// Creates new instance of struct [Light]
-    /// # Examples
-    /// ```
-    /// use ray_tracer::primitives::{Color, Light, Point};
-    ///
-    /// let light = Light::new(Point::default(), Color::BLACK);
-    ///
-    /// assert_eq!(light.position, Point::default());
-    /// assert_eq!(light.intensity, Color::BLACK);
-    /// ```
-    pub const fn new(position: Point, intensity: Color) -> Self {
+impl LightSample {
+    /// Creates [LightSample] of light at given position
+    pub fn towards(point: &Point, position: &Point, intensity: Color) -> Self {
+        let light_vector = *position - *point;
         return Self {
-            position,
+            direction: light_vector.normalized(),
+            distance: light_vector.magnitude(),
             intensity,
         };
     }
+
+    /// Returns [LightSample] with intensity reduced by [Attenuation] over its distance
+    pub fn attenuated(self, attenuation: &Attenuation) -> Self {
+        return Self {
+            intensity: self.intensity * attenuation.factor(self.distance),
+            ..self
+        };
+    }
 }
 
-impl CoarseEq for Light {
-    fn coarse_eq(&self, rhs: &Self) -> bool {
-        return std::ptr::eq(self, rhs)
-            || self.position.coarse_eq(&rhs.position) && self.intensity.coarse_eq(&rhs.intensity);
+pub trait Light: Debug + Send + Sync + DynPartialEq {
+    fn intensity(&self) -> Color;
+
+    /// Returns intensity of [Light] used for ambient term at [Point], it is reduced by the same
+    /// distance falloff as samples of [Light] but not by shadows or direction of light
+    fn ambient_intensity(&self, _point: &Point) -> Color {
+        return self.intensity();
     }
+
+    /// Returns number of samples of [Light] used for computing lighting and shadows
+    fn sample_count(&self) -> usize;
+
+    /// Returns sample with given index as seen from [Point], it may depend on the lit point so
+    /// that samples of area lights are spread differently for each point
+    fn sample(&self, point: &Point, index: usize) -> LightSample;
 }
 
-impl Default for Light {
-    fn default() -> Self {
-        return Self::new(Point::new(-10, 10, -10), Color::WHITE);
+impl PartialEq for dyn Light {
+    fn eq(&self, other: &Self) -> bool {
+        return self.dyn_eq(DynPartialEq::as_any(other));
     }
 }
 
-impl Display for Light {
-    fn fmt(&self, formatter: &mut Formatter) -> Result {
-        return formatter
-            .debug_struct("Light")
-            .field("position", &self.position)
-            .field("intensity", &self.intensity)
-            .finish();
+impl PartialEq<&Self> for Box<dyn Light> {
+    fn eq(&self, other: &&Self) -> bool {
+        return self == *other;
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::primitives::{Color, Light, Point};
+    use super::*;
+    use crate::primitives::{AreaLight, PointLight};
+
+    #[test]
+    fn compare_dyn_lights() {
+        let light_1: Box<dyn Light> = Box::new(PointLight::default());
+        let light_2: Box<dyn Light> = Box::new(PointLight::default());
+        let light_3: Box<dyn Light> = Box::new(AreaLight::new(
+            Point::new(-10, 10, -10),
+            Vector::RIGHT,
+            1,
+            Vector::UP,
+            1,
+            Color::WHITE,
+        ));
+        assert_eq!(light_1, light_2);
+        assert_ne!(light_1, light_3);
+    }
+
+    #[test]
+    fn sample_towards_position() {
+        let sample = LightSample::towards(&Point::ORIGIN, &Point::new(0, 0, -10), Color::WHITE);
+        assert_eq!(sample.direction, Vector::BACKWARD);
+        assert_eq!(sample.distance, 10.0);
+        assert_eq!(sample.intensity, Color::WHITE);
+    }
 
     #[test]
-    fn light_has_position_and_intensity() {
-        let position = Point::ORIGIN;
-        let intensity = Color::WHITE;
-        let light = Light::new(position, intensity);
-        assert_eq!(light.position, position);
-        assert_eq!(light.intensity, intensity);
+    fn attenuated_sample() {
+        let sample = LightSample::towards(&Point::ORIGIN, &Point::new(0, 2, 0), Color::WHITE);
+        let sample = sample.attenuated(&Attenuation::INVERSE_SQUARE);
+        assert_eq!(sample.intensity, Color::WHITE * 0.25);
+        assert_eq!(sample.distance, 2.0);
     }
 }
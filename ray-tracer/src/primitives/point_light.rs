@@ -0,0 +1,107 @@
+use crate::primitives::{Attenuation, Color, Light, LightSample, Point};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointLight {
+    pub position: Point,
+    pub intensity: Color,
+    pub attenuation: Attenuation,
+}
+
+impl PointLight {
+    /// Creates new instance of struct [PointLight]
+    /// # Examples
+    /// ```
+    /// use ray_tracer::primitives::{Color, Point, PointLight};
+    ///
+    /// let light = PointLight::new(Point::default(), Color::BLACK);
+    ///
+    /// assert_eq!(light.position, Point::default());
+    /// assert_eq!(light.intensity, Color::BLACK);
+    /// ```
+    pub const fn new(position: Point, intensity: Color) -> Self {
+        return Self {
+            position,
+            intensity,
+            attenuation: Attenuation::NONE,
+        };
+    }
+}
+
+impl Light for PointLight {
+    fn intensity(&self) -> Color {
+        return self.intensity;
+    }
+
+    fn ambient_intensity(&self, point: &Point) -> Color {
+        let distance = (self.position - *point).magnitude();
+        return self.intensity * self.attenuation.factor(distance);
+    }
+
+    fn sample_count(&self) -> usize {
+        return 1;
+    }
+
+    fn sample(&self, point: &Point, _index: usize) -> LightSample {
+        return LightSample::towards(point, &self.position, self.intensity)
+            .attenuated(&self.attenuation);
+    }
+}
+
+impl CoarseEq for PointLight {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.position.coarse_eq(&rhs.position)
+                && self.intensity.coarse_eq(&rhs.intensity)
+                && self.attenuation.coarse_eq(&rhs.attenuation);
+    }
+}
+
+impl Default for PointLight {
+    fn default() -> Self {
+        return Self::new(Point::new(-10, 10, -10), Color::WHITE);
+    }
+}
+
+impl Display for PointLight {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("PointLight")
+            .field("position", &self.position)
+            .field("intensity", &self.intensity)
+            .field("attenuation", &self.attenuation)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_has_position_and_intensity() {
+        let position = Point::ORIGIN;
+        let intensity = Color::WHITE;
+        let light = PointLight::new(position, intensity);
+        assert_eq!(light.position, position);
+        assert_eq!(light.intensity, intensity);
+    }
+
+    #[test]
+    fn point_light_without_attenuation_keeps_intensity() {
+        let light = PointLight::new(Point::new(0, 0, -100), Color::WHITE);
+        assert_eq!(light.attenuation, Attenuation::NONE);
+        assert_eq!(light.sample(&Point::ORIGIN, 0).intensity, Color::WHITE);
+    }
+
+    #[test]
+    fn attenuated_point_light_dims_with_distance() {
+        let mut light = PointLight::new(Point::ORIGIN, Color::WHITE);
+        light.attenuation = Attenuation::new(1.0, 0.5, 0.0);
+        let near_sample = light.sample(&Point::new(0, 0, 2), 0);
+        let far_sample = light.sample(&Point::new(0, 0, 6), 0);
+        assert_eq!(near_sample.intensity, Color::WHITE * 0.5);
+        assert_eq!(far_sample.intensity, Color::WHITE * 0.25);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::primitives::{Attenuation, Color, Light, LightSample, Point, Vector};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+/// Light shining from its position in a cone around its direction, intensity smoothly falls off
+/// to zero within soft edge at the border of the cone
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpotLight {
+    pub position: Point,
+    /// Direction of axis of the cone
+    pub direction: Vector,
+    /// Angle between axis and border of the cone in radians
+    pub cone_angle: f64,
+    /// Angular width of falloff inside border of the cone in radians, 0 gives hard edge
+    pub soft_edge: f64,
+    pub intensity: Color,
+    pub attenuation: Attenuation,
+}
+
+impl SpotLight {
+    pub fn new(
+        position: Point,
+        direction: Vector,
+        cone_angle: impl Into<f64>,
+        soft_edge: impl Into<f64>,
+        intensity: Color,
+    ) -> Self {
+        let cone_angle = cone_angle.into();
+        return Self {
+            position,
+            direction: direction.normalized(),
+            cone_angle,
+            soft_edge: soft_edge.into().clamp(0.0, cone_angle),
+            intensity,
+            attenuation: Attenuation::NONE,
+        };
+    }
+
+    /// Returns fraction of intensity reaching [Point], 1 inside the cone and 0 outside of it
+    pub fn falloff(&self, point: &Point) -> f64 {
+        let angle_cosine = (*point - self.position).normalized().dot(&self.direction);
+        let outer_cosine = self.cone_angle.cos();
+        let inner_cosine = (self.cone_angle - self.soft_edge).cos();
+        if angle_cosine >= inner_cosine {
+            return 1.0;
+        }
+        if angle_cosine <= outer_cosine {
+            return 0.0;
+        }
+        let ratio = (angle_cosine - outer_cosine) / (inner_cosine - outer_cosine);
+        return ratio * ratio * (3.0 - 2.0 * ratio);
+    }
+}
+
+impl Light for SpotLight {
+    fn intensity(&self) -> Color {
+        return self.intensity;
+    }
+
+    fn ambient_intensity(&self, point: &Point) -> Color {
+        let distance = (self.position - *point).magnitude();
+        return self.intensity * self.attenuation.factor(distance);
+    }
+
+    fn sample_count(&self) -> usize {
+        return 1;
+    }
+
+    fn sample(&self, point: &Point, _index: usize) -> LightSample {
+        let intensity = self.intensity * self.falloff(point);
+        return LightSample::towards(point, &self.position, intensity)
+            .attenuated(&self.attenuation);
+    }
+}
+
+impl CoarseEq for SpotLight {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.position.coarse_eq(&rhs.position)
+                && self.direction.coarse_eq(&rhs.direction)
+                && self.cone_angle.coarse_eq(&rhs.cone_angle)
+                && self.soft_edge.coarse_eq(&rhs.soft_edge)
+                && self.intensity.coarse_eq(&rhs.intensity)
+                && self.attenuation.coarse_eq(&rhs.attenuation);
+    }
+}
+
+impl Display for SpotLight {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("SpotLight")
+            .field("position", &self.position)
+            .field("direction", &self.direction)
+            .field("cone_angle", &self.cone_angle)
+            .field("soft_edge", &self.soft_edge)
+            .field("intensity", &self.intensity)
+            .field("attenuation", &self.attenuation)
+            .finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::PI;
+    use rstest::rstest;
+
+    fn default_spot_light() -> SpotLight {
+        return SpotLight::new(
+            Point::new(0, 10, 0),
+            Vector::DOWN,
+            PI / 4.0,
+            PI / 8.0,
+            Color::WHITE,
+        );
+    }
+
+    #[test]
+    fn soft_edge_is_limited_by_cone_angle() {
+        let light = SpotLight::new(Point::ORIGIN, Vector::DOWN, 0.5, 2, Color::WHITE);
+        assert_eq!(light.soft_edge, 0.5);
+    }
+
+    #[rstest]
+    #[case(Point::ORIGIN, 1.0)]
+    #[case(Point::new(2, 0, 0), 1.0)]
+    #[case(Point::new(12, 0, 0), 0.0)]
+    #[case(Point::new(20, 0, 0), 0.0)]
+    #[case(Point::new(0, 20, 0), 0.0)]
+    fn spot_light_falloff(#[case] point: Point, #[case] expected: f64) {
+        assert_eq!(default_spot_light().falloff(&point), expected);
+    }
+
+    #[test]
+    fn spot_light_falloff_is_smooth_within_soft_edge() {
+        let light = default_spot_light();
+        let falloffs: Vec<f64> = (5..10)
+            .map(|x| light.falloff(&Point::new(x, 0, 0)))
+            .collect();
+        assert!(falloffs.iter().all(|falloff| (0.0..1.0).contains(falloff)));
+        assert!(falloffs.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn spot_light_sample_is_reduced_by_falloff() {
+        let light = default_spot_light();
+        let sample = light.sample(&Point::new(20, 0, 0), 0);
+        assert_eq!(sample.intensity, Color::BLACK);
+        let sample = light.sample(&Point::ORIGIN, 0);
+        assert_eq!(sample.direction, Vector::UP);
+        assert_eq!(sample.distance, 10.0);
+        assert_eq!(sample.intensity, Color::WHITE);
+    }
+}
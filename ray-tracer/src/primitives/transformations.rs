@@ -54,6 +54,37 @@ pub fn rotation_z(theta: impl Into<f64>) -> Transformation {
     return result;
 }
 
+/// Returns rotation by given angle around axis going through origin, rotation follows right hand
+/// rule just like [rotation_x], [rotation_y] and [rotation_z]
+pub fn rotation(axis: Vector, theta: impl Into<f64>) -> Transformation {
+    let theta = theta.into();
+    let Vector { x, y, z } = axis.normalized();
+    let cos = theta.cos();
+    let sin = theta.sin();
+    let versine = 1.0 - cos;
+    return Transformation::new([
+        [
+            cos + x * x * versine,
+            x * y * versine - z * sin,
+            x * z * versine + y * sin,
+            0.0,
+        ],
+        [
+            y * x * versine + z * sin,
+            cos + y * y * versine,
+            y * z * versine - x * sin,
+            0.0,
+        ],
+        [
+            z * x * versine - y * sin,
+            z * y * versine + x * sin,
+            cos + z * z * versine,
+            0.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+}
+
 pub fn shearing(
     xy: impl Into<f64>,
     xz: impl Into<f64>,
@@ -188,6 +219,21 @@ mod tests {
         assert!((full_quarter_rotation * point).coarse_eq(&Point::new(-1, 0, 0)));
     }
 
+    #[test]
+    fn rotation_around_coordinate_axes() {
+        let theta = PI / 3.0;
+        assert!(rotation(Vector::RIGHT, theta).coarse_eq(&rotation_x(theta)));
+        assert!(rotation(Vector::UP, theta).coarse_eq(&rotation_y(theta)));
+        assert!(rotation(Vector::new(0, 0, 2), theta).coarse_eq(&rotation_z(theta)));
+    }
+
+    #[test]
+    fn rotation_around_arbitrary_axis() {
+        let rotation = rotation(Vector::new(1, 1, 1), 2.0 * PI / 3.0);
+        assert!((rotation * Point::new(1, 0, 0)).coarse_eq(&Point::new(0, 1, 0)));
+        assert!((rotation * Point::new(0, 1, 0)).coarse_eq(&Point::new(0, 0, 1)));
+    }
+
     #[test]
     fn point_shearing() {
         let shearing_transformation = shearing(1, 0, 0, 0, 0, 0);
@@ -102,6 +102,18 @@ impl Vector {
         );
     }
 
+    /// Returns two unit vectors which together with normalized [Vector] form orthonormal basis
+    pub fn orthonormal_basis(&self) -> (Self, Self) {
+        let helper = if self.x.abs() > 0.9 {
+            Self::UP
+        } else {
+            Self::RIGHT
+        };
+        let tangent = helper.cross(self).normalized();
+        let bitangent = self.cross(&tangent).normalized();
+        return (tangent, bitangent);
+    }
+
     pub fn reflect(&self, normal: &Self) -> Self {
         return *self - (*normal * 2.0_f64 * self.dot(normal));
     }
@@ -377,6 +389,19 @@ mod tests {
         assert_eq!(vector2.cross(&vector1), Vector::new(1, -2, 1));
     }
 
+    #[rstest]
+    #[case(Vector::UP)]
+    #[case(Vector::RIGHT)]
+    #[case(Vector::new(1, -2, 3).normalized())]
+    fn orthonormal_basis_of_vector(#[case] vector: Vector) {
+        let (tangent, bitangent) = vector.orthonormal_basis();
+        assert!(tangent.magnitude().coarse_eq(&1.0));
+        assert!(bitangent.magnitude().coarse_eq(&1.0));
+        assert!(tangent.dot(&vector).coarse_eq(&0.0));
+        assert!(bitangent.dot(&vector).coarse_eq(&0.0));
+        assert!(tangent.dot(&bitangent).coarse_eq(&0.0));
+    }
+
     #[test]
     fn reflect_vector() {
         let vector = Vector::new(1, -1, 0);
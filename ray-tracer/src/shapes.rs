@@ -1,17 +1,24 @@
 pub use cone::Cone;
+pub use csg::{Csg, CsgOperation};
 pub use cube::Cube;
 pub use cylinder::Cylinder;
+pub use group::Group;
 pub use plane::Plane;
+pub use shape::Bounds;
 pub use shape::Intersect;
 pub use shape::Shape;
 pub use shape::Transform;
+pub use smooth_triangle::SmoothTriangle;
 pub use sphere::Sphere;
 pub use triangle::Triangle;
 
 mod cone;
+mod csg;
 mod cube;
 mod cylinder;
+mod group;
 mod plane;
 mod shape;
+mod smooth_triangle;
 mod sphere;
 mod triangle;
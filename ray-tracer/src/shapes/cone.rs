@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::consts::{EPSILON, MAX, MIN};
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::{CoarseEq, Squared, solve_quadratic};
@@ -9,6 +9,7 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Cone {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
     pub min: f64,
     pub max: f64,
     pub closed: bool,
@@ -24,6 +25,7 @@ impl Cone {
     ) -> Self {
         return Self {
             transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
             material,
             min: min.into(),
             max: max.into(),
@@ -75,6 +77,12 @@ impl Transform for Cone {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
 }
 
 impl Intersect for Cone {
@@ -112,6 +120,18 @@ impl Intersect for Cone {
     }
 }
 
+impl Bounds for Cone {
+    fn local_bounds(&self) -> BoundingBox {
+        let min = BoundingBox::unbounded_min(self.min);
+        let max = BoundingBox::unbounded_max(self.max);
+        let radius = min.abs().max(max.abs());
+        return BoundingBox::new(
+            Point::new(-radius, min, -radius),
+            Point::new(radius, max, radius),
+        );
+    }
+}
+
 impl Shape for Cone {
     fn local_normal_at(&self, point: Point) -> Vector {
         let distance = point.x.squared() + point.z.squared();
@@ -135,6 +155,14 @@ impl Shape for Cone {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Default for Cone {
@@ -158,6 +186,7 @@ impl Display for Cone {
             .field("closed", &self.closed)
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -170,6 +199,7 @@ impl CoarseEq for Cone {
                 && self
                     .transformation_inverse
                     .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
                 && self.min.coarse_eq(&rhs.min)
                 && self.max.coarse_eq(&rhs.max);
     }
@@ -250,4 +280,14 @@ mod tests {
         let normal = cone.local_normal_at(point);
         assert_eq!(normal, expected_normal);
     }
+
+    #[test]
+    fn cone_bounds() {
+        let bounds = Cone::default().local_bounds();
+        assert_eq!(bounds, BoundingBox::INFINITE);
+        let cone = Cone::new(Material::default(), Transformation::IDENTITY, -5, 3, true);
+        let bounds = cone.local_bounds();
+        assert_eq!(bounds.min, Point::new(-5, -5, -5));
+        assert_eq!(bounds.max, Point::new(5, 3, 5));
+    }
 }
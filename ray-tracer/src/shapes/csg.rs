@@ -0,0 +1,405 @@
+use super::shape::composite_local_normal_at;
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersections, Material, Motion, Ray};
+use crate::primitives::{Point, Transformation, Vector};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl CsgOperation {
+    /// Returns whether intersection with operand is kept in result of operation, based on which
+    /// operand was hit and whether hit happened inside of either operand
+    pub const fn allows_intersection(
+        &self,
+        is_left_hit: bool,
+        is_inside_left: bool,
+        is_inside_right: bool,
+    ) -> bool {
+        return match self {
+            Self::Union => (is_left_hit && !is_inside_right) || (!is_left_hit && !is_inside_left),
+            Self::Intersection => {
+                (is_left_hit && is_inside_right) || (!is_left_hit && is_inside_left)
+            }
+            Self::Difference => {
+                (is_left_hit && !is_inside_right) || (!is_left_hit && is_inside_left)
+            }
+        };
+    }
+}
+
+/// Shape made by combining two operands with [CsgOperation], like [Group](super::Group) it folds
+/// its transformation into operands
+#[derive(Debug, PartialEq)]
+pub struct Csg {
+    pub material: Material,
+    transformation_inverse: Transformation,
+    motion: Motion,
+    pub operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+    bounds: BoundingBox,
+}
+
+impl Csg {
+    pub fn new(
+        material: Material,
+        transformation: Transformation,
+        operation: CsgOperation,
+        mut left: Box<dyn Shape>,
+        mut right: Box<dyn Shape>,
+    ) -> Self {
+        left.set_transformation(transformation * left.transformation());
+        right.set_transformation(transformation * right.transformation());
+        let mut csg = Self {
+            material,
+            transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
+            operation,
+            left,
+            right,
+            bounds: BoundingBox::EMPTY,
+        };
+        csg.update_bounds();
+        return csg;
+    }
+
+    pub fn left(&self) -> &dyn Shape {
+        return self.left.as_ref();
+    }
+
+    pub fn right(&self) -> &dyn Shape {
+        return self.right.as_ref();
+    }
+
+    fn update_bounds(&mut self) {
+        self.bounds = self.left.bounds().merge(&self.right.bounds());
+    }
+
+    fn update_operands(&mut self, transformation_inverse: Transformation) {
+        let change = transformation_inverse.inverse() * self.transformation_inverse;
+        self.transformation_inverse = transformation_inverse;
+        self.left
+            .set_transformation(change * self.left.transformation());
+        self.right
+            .set_transformation(change * self.right.transformation());
+        self.update_bounds();
+    }
+
+    /// Removes intersections starting at given index which are not part of surface of [Csg],
+    /// remaining intersections are left sorted
+    fn filter_intersections(&self, intersections: &mut Intersections, start: usize) {
+        intersections[start..].sort();
+        let mut is_inside_left = false;
+        let mut is_inside_right = false;
+        let mut kept = start;
+        for index in start..intersections.len() {
+            let is_left_hit = self.left.includes(intersections[index].shape);
+            if self
+                .operation
+                .allows_intersection(is_left_hit, is_inside_left, is_inside_right)
+            {
+                intersections.swap(kept, index);
+                kept += 1;
+            }
+            if is_left_hit {
+                is_inside_left = !is_inside_left;
+            } else {
+                is_inside_right = !is_inside_right;
+            }
+        }
+        intersections.truncate(kept);
+    }
+}
+
+impl Transform for Csg {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.update_operands(transformation.inverse());
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.update_operands(transformation);
+    }
+}
+
+impl Intersect for Csg {
+    fn local_intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        self.intersect(&ray.transform(&self.transformation()), intersections);
+    }
+
+    fn intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        if !self.bounds.is_intersected_by(ray) {
+            return;
+        }
+        let start = intersections.len();
+        ray.intersect(self.left.as_ref(), intersections);
+        ray.intersect(self.right.as_ref(), intersections);
+        self.filter_intersections(intersections, start);
+    }
+}
+
+impl Bounds for Csg {
+    fn local_bounds(&self) -> BoundingBox {
+        return self.bounds.transform(&self.transformation_inverse);
+    }
+}
+
+impl Shape for Csg {
+    /// Intersections always refer to operands of [Csg], when asked directly it returns normal of
+    /// the operand nearest to the point
+    fn local_normal_at(&self, point: Point) -> Vector {
+        let operands = [self.left.as_ref(), self.right.as_ref()];
+        return composite_local_normal_at(self.transformation(), operands, point);
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        return self.bounds;
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+        self.left.set_motion(motion);
+        self.right.set_motion(motion);
+        self.update_bounds();
+    }
+
+    fn includes(&self, shape: &dyn Shape) -> bool {
+        return self.left.includes(shape) || self.right.includes(shape);
+    }
+}
+
+impl Display for Csg {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("Csg")
+            .field("material", &self.material)
+            .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
+            .field("operation", &self.operation)
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish();
+    }
+}
+
+impl CoarseEq for Csg {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.material == rhs.material
+                && self
+                    .transformation_inverse
+                    .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
+                && self.operation == rhs.operation
+                && self.left == rhs.left
+                && self.right == rhs.right;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composites::Intersection;
+    use crate::primitives::transformations;
+    use crate::shapes::{Cube, Sphere};
+    use rstest::rstest;
+
+    fn csg_of_sphere_and_cube(operation: CsgOperation) -> Csg {
+        return Csg::new(
+            Material::default(),
+            Transformation::IDENTITY,
+            operation,
+            Box::new(Sphere::default()),
+            Box::new(Cube::default()),
+        );
+    }
+
+    #[rstest]
+    #[case(CsgOperation::Union, true, true, true, false)]
+    #[case(CsgOperation::Union, true, true, false, true)]
+    #[case(CsgOperation::Union, true, false, true, false)]
+    #[case(CsgOperation::Union, true, false, false, true)]
+    #[case(CsgOperation::Union, false, true, true, false)]
+    #[case(CsgOperation::Union, false, true, false, false)]
+    #[case(CsgOperation::Union, false, false, true, true)]
+    #[case(CsgOperation::Union, false, false, false, true)]
+    #[case(CsgOperation::Intersection, true, true, true, true)]
+    #[case(CsgOperation::Intersection, true, true, false, false)]
+    #[case(CsgOperation::Intersection, true, false, true, true)]
+    #[case(CsgOperation::Intersection, true, false, false, false)]
+    #[case(CsgOperation::Intersection, false, true, true, true)]
+    #[case(CsgOperation::Intersection, false, true, false, true)]
+    #[case(CsgOperation::Intersection, false, false, true, false)]
+    #[case(CsgOperation::Intersection, false, false, false, false)]
+    #[case(CsgOperation::Difference, true, true, true, false)]
+    #[case(CsgOperation::Difference, true, true, false, true)]
+    #[case(CsgOperation::Difference, true, false, true, false)]
+    #[case(CsgOperation::Difference, true, false, false, true)]
+    #[case(CsgOperation::Difference, false, true, true, true)]
+    #[case(CsgOperation::Difference, false, true, false, true)]
+    #[case(CsgOperation::Difference, false, false, true, false)]
+    #[case(CsgOperation::Difference, false, false, false, false)]
+    fn evaluating_csg_operation(
+        #[case] operation: CsgOperation,
+        #[case] is_left_hit: bool,
+        #[case] is_inside_left: bool,
+        #[case] is_inside_right: bool,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            operation.allows_intersection(is_left_hit, is_inside_left, is_inside_right),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(CsgOperation::Union, 0, 3)]
+    #[case(CsgOperation::Intersection, 1, 2)]
+    #[case(CsgOperation::Difference, 0, 1)]
+    fn filtering_intersections(
+        #[case] operation: CsgOperation,
+        #[case] first_index: usize,
+        #[case] second_index: usize,
+    ) {
+        let csg = csg_of_sphere_and_cube(operation);
+        let intersections = [
+            Intersection::new(1, csg.left()),
+            Intersection::new(2, csg.right()),
+            Intersection::new(3, csg.left()),
+            Intersection::new(4, csg.right()),
+        ];
+        let mut filtered = Intersections::from(intersections.clone());
+        csg.filter_intersections(&mut filtered, 0);
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0], intersections[first_index]);
+        assert_eq!(filtered[1], intersections[second_index]);
+    }
+
+    #[test]
+    fn normal_on_csg_comes_from_nearest_operand() {
+        let csg = Csg::new(
+            Material::default(),
+            transformations::translation(0, 3, 0),
+            CsgOperation::Difference,
+            Box::new(Cube::default()),
+            Box::new(Sphere::new(
+                Material::default(),
+                transformations::translation(4, 0, 0),
+            )),
+        );
+        assert!(csg.normal_at(Point::new(0, 4, 0)).coarse_eq(&Vector::UP));
+        assert!(csg.normal_at(Point::new(5, 3, 0)).coarse_eq(&Vector::RIGHT));
+    }
+
+    #[test]
+    fn ray_misses_csg() {
+        let csg = csg_of_sphere_and_cube(CsgOperation::Union);
+        let ray = Ray::new(Point::new(0, 2, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        csg.local_intersect(&ray, &mut intersections);
+        assert!(intersections.is_empty());
+    }
+
+    #[test]
+    fn ray_hits_csg() {
+        let csg = Csg::new(
+            Material::default(),
+            Transformation::IDENTITY,
+            CsgOperation::Union,
+            Box::new(Sphere::default()),
+            Box::new(Sphere::new(
+                Material::default(),
+                transformations::translation(0, 0, 0.5),
+            )),
+        );
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        csg.local_intersect(&ray, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].distance, 4.0);
+        assert_eq!(intersections[0].shape, csg.left());
+        assert_eq!(intersections[1].distance, 6.5);
+        assert_eq!(intersections[1].shape, csg.right());
+    }
+
+    #[test]
+    fn filtering_keeps_preceding_intersections() {
+        let csg = Csg::new(
+            Material::default(),
+            Transformation::IDENTITY,
+            CsgOperation::Difference,
+            Box::new(Cube::default()),
+            Box::new(Sphere::new(
+                Material::default(),
+                transformations::scaling(0.5, 0.5, 0.5),
+            )),
+        );
+        let sphere = Sphere::default();
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::from(Intersection::new(10, &sphere));
+        ray.intersect(&csg, &mut intersections);
+        let distances: Vec<f64> = intersections
+            .iter()
+            .map(|intersection| intersection.distance)
+            .collect();
+        assert_eq!(distances, vec![10.0, 4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn nested_csg_as_operand() {
+        let inner_csg = Csg::new(
+            Material::default(),
+            Transformation::IDENTITY,
+            CsgOperation::Difference,
+            Box::new(Cube::default()),
+            Box::new(Sphere::new(
+                Material::default(),
+                transformations::scaling(0.5, 0.5, 0.5),
+            )),
+        );
+        let csg = Csg::new(
+            Material::default(),
+            transformations::translation(0, 0, 1),
+            CsgOperation::Intersection,
+            Box::new(inner_csg),
+            Box::new(Sphere::new(
+                Material::default(),
+                transformations::scaling(1.2, 1.2, 1.2),
+            )),
+        );
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        ray.intersect(&csg, &mut intersections);
+        let distances: Vec<f64> = intersections
+            .iter()
+            .map(|intersection| intersection.distance)
+            .collect();
+        assert_eq!(distances, vec![5.0, 5.5, 6.5, 7.0]);
+        assert!(
+            intersections
+                .iter()
+                .all(|intersection| csg.left().includes(intersection.shape))
+        );
+    }
+}
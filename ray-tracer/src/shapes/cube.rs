@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::consts::{EPSILON, MAX, MIN};
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::CoarseEq;
@@ -9,6 +9,7 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Cube {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
 }
 
 impl Cube {
@@ -16,6 +17,7 @@ impl Cube {
         return Self {
             material,
             transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
         };
     }
 
@@ -59,6 +61,12 @@ impl Transform for Cube {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
 }
 
 impl Intersect for Cube {
@@ -85,6 +93,12 @@ impl Intersect for Cube {
     }
 }
 
+impl Bounds for Cube {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+    }
+}
+
 impl Shape for Cube {
     fn local_normal_at(&self, point: Point) -> Vector {
         let abs_point = point.abs();
@@ -103,6 +117,14 @@ impl Shape for Cube {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Default for Cube {
@@ -117,6 +139,7 @@ impl Display for Cube {
             .debug_struct("Cube")
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -127,7 +150,8 @@ impl CoarseEq for Cube {
             || self.material == rhs.material
                 && self
                     .transformation_inverse
-                    .coarse_eq(&rhs.transformation_inverse);
+                    .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion);
     }
 }
 
@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::consts::{EPSILON, MAX, MIN};
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::{CoarseEq, Squared, solve_quadratic};
@@ -9,6 +9,7 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Cylinder {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
     pub min: f64,
     pub max: f64,
     pub closed: bool,
@@ -25,6 +26,7 @@ impl Cylinder {
         return Self {
             material,
             transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
             min: min.into(),
             max: max.into(),
             closed,
@@ -75,6 +77,12 @@ impl Transform for Cylinder {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
 }
 
 impl Intersect for Cylinder {
@@ -110,6 +118,15 @@ impl Intersect for Cylinder {
     }
 }
 
+impl Bounds for Cylinder {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::new(
+            Point::new(-1, BoundingBox::unbounded_min(self.min), -1),
+            Point::new(1, BoundingBox::unbounded_max(self.max), 1),
+        );
+    }
+}
+
 impl Shape for Cylinder {
     fn local_normal_at(&self, point: Point) -> Vector {
         let distance = point.x.squared() + point.z.squared();
@@ -128,6 +145,14 @@ impl Shape for Cylinder {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Default for Cylinder {
@@ -151,6 +176,7 @@ impl Display for Cylinder {
             .field("closed", &self.closed)
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -163,6 +189,7 @@ impl CoarseEq for Cylinder {
                 && self
                     .transformation_inverse
                     .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
                 && self.min.coarse_eq(&rhs.min)
                 && self.max.coarse_eq(&rhs.max);
     }
@@ -285,4 +312,15 @@ mod tests {
         cylinder.closed = true;
         assert_eq!(cylinder.local_normal_at(point), normal);
     }
+
+    #[test]
+    fn cylinder_bounds() {
+        let bounds = Cylinder::default().local_bounds();
+        assert_eq!(bounds.min, Point::new(-1, f64::NEG_INFINITY, -1));
+        assert_eq!(bounds.max, Point::new(1, f64::INFINITY, 1));
+        let cylinder = Cylinder::new(Material::default(), Transformation::IDENTITY, -5, 3, true);
+        let bounds = cylinder.local_bounds();
+        assert_eq!(bounds.min, Point::new(-1, -5, -1));
+        assert_eq!(bounds.max, Point::new(1, 3, 1));
+    }
 }
@@ -0,0 +1,379 @@
+use super::shape::composite_local_normal_at;
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{
+    BoundingBox, BoundingVolumeHierarchy, Intersections, Material, Motion, Ray,
+};
+use crate::primitives::{Point, Transformation, Vector};
+use crate::utils::CoarseEq;
+use core::fmt::{Display, Formatter, Result};
+use std::sync::OnceLock;
+
+/// Shape composed of child shapes which are transformed together with it, transformation of
+/// [Group] is folded into transformations of its children when they are added, so intersections
+/// and normals are computed by children directly, groups with many children such as meshes
+/// intersect them through [BoundingVolumeHierarchy] built on first use
+#[derive(Debug)]
+pub struct Group {
+    pub material: Material,
+    transformation_inverse: Transformation,
+    motion: Motion,
+    children: Vec<Box<dyn Shape>>,
+    bounds: BoundingBox,
+    bvh: OnceLock<BoundingVolumeHierarchy>,
+}
+
+impl Group {
+    pub fn new(material: Material, transformation: Transformation) -> Self {
+        return Self {
+            material,
+            transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
+            children: Vec::new(),
+            bounds: BoundingBox::EMPTY,
+            bvh: OnceLock::new(),
+        };
+    }
+
+    /// Adds child to [Group], transformation and motion of child are treated as relative to
+    /// [Group], motion of moving [Group] replaces motion of child
+    pub fn add_child(&mut self, mut child: Box<dyn Shape>) {
+        if !self.motion.is_static() {
+            child.set_motion(self.motion);
+        } else if !child.motion().is_static() {
+            child.set_motion(child.motion().transform(&self.transformation()));
+        }
+        child.set_transformation(self.transformation() * child.transformation());
+        self.bounds = self.bounds.merge(&child.bounds());
+        self.children.push(child);
+        self.bvh.take();
+    }
+
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        return &self.children;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.children.is_empty();
+    }
+
+    fn update_children(&mut self, transformation_inverse: Transformation) {
+        let change = transformation_inverse.inverse() * self.transformation_inverse;
+        self.transformation_inverse = transformation_inverse;
+        self.bounds = BoundingBox::EMPTY;
+        for child in &mut self.children {
+            child.set_transformation(change * child.transformation());
+            self.bounds = self.bounds.merge(&child.bounds());
+        }
+        self.bvh.take();
+    }
+}
+
+impl Transform for Group {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.update_children(transformation.inverse());
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.update_children(transformation);
+    }
+}
+
+impl Intersect for Group {
+    fn local_intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        self.intersect(&ray.transform(&self.transformation()), intersections);
+    }
+
+    fn intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        if !self.bounds.is_intersected_by(ray) {
+            return;
+        }
+        if self.children.len() <= BoundingVolumeHierarchy::MAX_LEAF_SIZE {
+            for child in &self.children {
+                ray.intersect(child.as_ref(), intersections);
+            }
+            return;
+        }
+        let bvh = self
+            .bvh
+            .get_or_init(|| BoundingVolumeHierarchy::new(&self.children));
+        bvh.intersect(ray, &self.children, intersections);
+    }
+}
+
+impl Bounds for Group {
+    fn local_bounds(&self) -> BoundingBox {
+        return self.bounds.transform(&self.transformation_inverse);
+    }
+}
+
+impl Shape for Group {
+    /// Intersections always refer to children of [Group], when asked directly it returns normal
+    /// of the child nearest to the point
+    fn local_normal_at(&self, point: Point) -> Vector {
+        let children = self.children.iter().map(Box::as_ref);
+        return composite_local_normal_at(self.transformation(), children, point);
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        return self.bounds;
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    /// Children move together with [Group], so its motion is passed to all of them
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+        self.bounds = BoundingBox::EMPTY;
+        for child in &mut self.children {
+            child.set_motion(motion);
+            self.bounds = self.bounds.merge(&child.bounds());
+        }
+        self.bvh.take();
+    }
+
+    fn includes(&self, shape: &dyn Shape) -> bool {
+        return self.children.iter().any(|child| child.includes(shape));
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        return Self::new(Material::default(), Transformation::IDENTITY);
+    }
+}
+
+impl PartialEq for Group {
+    fn eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.material == rhs.material
+                && self.transformation_inverse == rhs.transformation_inverse
+                && self.motion == rhs.motion
+                && self.children == rhs.children;
+    }
+}
+
+impl Display for Group {
+    fn fmt(&self, formatter: &mut Formatter) -> Result {
+        return formatter
+            .debug_struct("Group")
+            .field("material", &self.material)
+            .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
+            .field("children", &self.children)
+            .finish();
+    }
+}
+
+impl CoarseEq for Group {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.material == rhs.material
+                && self
+                    .transformation_inverse
+                    .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
+                && self.children == rhs.children;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::PI;
+    use crate::primitives::transformations;
+    use crate::shapes::Sphere;
+
+    #[test]
+    fn default_group() {
+        let group = Group::default();
+        assert_eq!(group.transformation_inverse, Transformation::IDENTITY);
+        assert!(group.is_empty());
+    }
+
+    #[test]
+    fn adding_child_to_group() {
+        let mut group = Group::new(Material::default(), transformations::translation(1, 0, 0));
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::scaling(2, 2, 2),
+        )));
+        assert_eq!(group.children().len(), 1);
+        assert!(group.children()[0].transformation().coarse_eq(
+            &(transformations::translation(1, 0, 0) * transformations::scaling(2, 2, 2))
+        ));
+    }
+
+    #[test]
+    fn intersecting_ray_with_empty_group() {
+        let group = Group::default();
+        let ray = Ray::new(Point::ORIGIN, Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        group.local_intersect(&ray, &mut intersections);
+        assert!(intersections.is_empty());
+    }
+
+    #[test]
+    fn intersecting_ray_with_group() {
+        let mut group = Group::default();
+        group.add_child(Box::new(Sphere::default()));
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(0, 0, -3),
+        )));
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(5, 0, 0),
+        )));
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        group.local_intersect(&ray, &mut intersections);
+        intersections.sort();
+        assert_eq!(intersections.len(), 4);
+        assert_eq!(intersections[0].shape, group.children()[1].as_ref());
+        assert_eq!(intersections[1].shape, group.children()[1].as_ref());
+        assert_eq!(intersections[2].shape, group.children()[0].as_ref());
+        assert_eq!(intersections[3].shape, group.children()[0].as_ref());
+    }
+
+    #[test]
+    fn intersecting_transformed_group() {
+        let mut group = Group::new(Material::default(), transformations::scaling(2, 2, 2));
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(5, 0, 0),
+        )));
+        let ray = Ray::new(Point::new(10, 0, -10), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        ray.intersect(&group, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+    }
+
+    #[test]
+    fn intersecting_group_with_many_children() {
+        let mut group = Group::new(Material::default(), transformations::translation(0, 1, 0));
+        for index in 0..50 {
+            group.add_child(Box::new(Sphere::new(
+                Material::default(),
+                transformations::translation(index * 3, 0, 0),
+            )));
+        }
+        let ray = Ray::new(Point::new(30, 1, -5), Vector::FORWARD);
+        {
+            let mut intersections = Intersections::new();
+            ray.intersect(&group, &mut intersections);
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections[0].shape, group.children()[10].as_ref());
+        }
+        assert!(group.bvh.get().is_some());
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(30, 0, 5),
+        )));
+        assert!(group.bvh.get().is_none());
+        let mut intersections = Intersections::new();
+        ray.intersect(&group, &mut intersections);
+        assert_eq!(intersections.len(), 4);
+    }
+
+    #[test]
+    fn changing_group_transformation_moves_children() {
+        let mut group = Group::default();
+        group.add_child(Box::new(Sphere::default()));
+        group.set_transformation(transformations::translation(0, 3, 0));
+        let ray = Ray::new(Point::new(0, 3, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        ray.intersect(&group, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+        assert!(group.bounds().coarse_eq(&BoundingBox::new(
+            Point::new(-1, 2, -1),
+            Point::new(1, 4, 1)
+        )));
+    }
+
+    #[test]
+    fn normal_on_child_of_nested_groups() {
+        let mut group_1 = Group::new(Material::default(), transformations::rotation_y(PI / 2.0));
+        let mut group_2 = Group::new(Material::default(), transformations::scaling(1, 2, 3));
+        group_2.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(5, 0, 0),
+        )));
+        group_1.add_child(Box::new(group_2));
+        let ray = Ray::new(Point::new(-10, 0, -5.5774), Vector::RIGHT);
+        let mut intersections = Intersections::new();
+        ray.intersect(&group_1, &mut intersections);
+        let sphere = intersections[0].shape;
+        let normal = sphere.normal_at(Point::new(1.7321, 1.1547, -5.5774));
+        let expected = Vector::new(0.2857, 0.4286, -0.8571);
+        assert!((normal - expected).magnitude() < 0.0001);
+    }
+
+    #[test]
+    fn normal_on_group_comes_from_nearest_child() {
+        let mut group = Group::new(Material::default(), transformations::rotation_y(PI / 2.0));
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(5, 0, 0),
+        )));
+        group.add_child(Box::new(Sphere::default()));
+        let normal = group.normal_at(Point::new(0, 0, -6));
+        assert!(normal.coarse_eq(&Vector::BACKWARD));
+        let normal = group.normal_at(Point::new(0, 1, 0));
+        assert!(normal.coarse_eq(&Vector::UP));
+        assert_eq!(Group::default().normal_at(Point::ORIGIN), Vector::UP);
+    }
+
+    #[test]
+    fn group_includes_its_descendants() {
+        let mut inner_group = Group::default();
+        inner_group.add_child(Box::new(Sphere::default()));
+        let mut group = Group::default();
+        group.add_child(Box::new(inner_group));
+        let other_sphere = Sphere::default();
+        let ray = Ray::new(Point::new(0, 0, -5), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        ray.intersect(&group, &mut intersections);
+        assert!(group.includes(intersections[0].shape));
+        assert!(!group.includes(&other_sphere));
+    }
+
+    #[test]
+    fn moving_group_moves_children() {
+        let motion = Motion::new(Vector::new(0, 2, 0), Vector::ZERO, Point::ORIGIN);
+        let mut group = Group::default();
+        group.add_child(Box::new(Sphere::default()));
+        group.set_motion(motion);
+        group.add_child(Box::new(Sphere::new(
+            Material::default(),
+            transformations::translation(3, 0, 0),
+        )));
+        assert!(
+            group
+                .children()
+                .iter()
+                .all(|child| *child.motion() == motion)
+        );
+        assert_eq!(
+            group.bounds(),
+            BoundingBox::new(Point::new(-1, -1, -1), Point::new(4, 3, 1))
+        );
+        let ray = Ray::with_time(Point::new(0, 2, -5), Vector::FORWARD, 1.0);
+        let mut intersections = Intersections::new();
+        ray.intersect(&group, &mut intersections);
+        assert_eq!(intersections.len(), 2);
+    }
+}
@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::consts::EPSILON;
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::CoarseEq;
@@ -9,6 +9,7 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Plane {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
 }
 
 impl Plane {
@@ -16,6 +17,7 @@ impl Plane {
         return Self {
             material,
             transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
         };
     }
 }
@@ -36,6 +38,12 @@ impl Transform for Plane {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
 }
 
 impl Intersect for Plane {
@@ -48,6 +56,15 @@ impl Intersect for Plane {
     }
 }
 
+impl Bounds for Plane {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::new(
+            Point::new(f64::NEG_INFINITY, 0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0, f64::INFINITY),
+        );
+    }
+}
+
 impl Shape for Plane {
     fn local_normal_at(&self, _: Point) -> Vector {
         return Vector::UP;
@@ -56,6 +73,14 @@ impl Shape for Plane {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Default for Plane {
@@ -70,6 +95,7 @@ impl Display for Plane {
             .debug_struct("Plane")
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -80,7 +106,8 @@ impl CoarseEq for Plane {
             || self.material == rhs.material
                 && self
                     .transformation_inverse
-                    .coarse_eq(&rhs.transformation_inverse);
+                    .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion);
     }
 }
 
@@ -134,4 +161,14 @@ mod tests {
         assert_eq!(intersections[0].distance, 1.0);
         assert_eq!(intersections[0].shape, boxed_shape.as_ref());
     }
+
+    #[test]
+    fn plane_bounds() {
+        let bounds = Plane::default().local_bounds();
+        assert_eq!(
+            bounds.min,
+            Point::new(f64::NEG_INFINITY, 0, f64::NEG_INFINITY)
+        );
+        assert_eq!(bounds.max, Point::new(f64::INFINITY, 0, f64::INFINITY));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::composites::{Intersections, Material, Ray};
+use crate::composites::{BoundingBox, Intersections, Material, Motion, Ray};
 use crate::dyn_partial_eq::DynPartialEq;
 use crate::primitives::{Point, Transformation, Vector};
 use core::fmt::Debug;
@@ -11,24 +11,99 @@ pub trait Transform {
     fn transformation_inverse(&self) -> Transformation;
 
     fn set_transformation_inverse(&mut self, transformation: Transformation);
+
+    /// Returns inverse of transformation at given time, it differs from
+    /// [Transform::transformation_inverse] only for moving objects
+    #[inline]
+    fn transformation_inverse_at(&self, _time: f64) -> Transformation {
+        return self.transformation_inverse();
+    }
 }
 
-pub trait Intersect {
+pub trait Intersect: Transform {
     fn local_intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>);
+
+    /// Intersects [Ray] given in parent space with shape by transforming it into object space
+    #[inline]
+    fn intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        let local_ray = ray.transform(&self.transformation_inverse_at(ray.time));
+        self.local_intersect(&local_ray, intersections);
+    }
+}
+
+pub trait Bounds {
+    /// Returns box enclosing shape in object space, infinite shapes have infinite extent
+    /// along their unbounded axes
+    fn local_bounds(&self) -> BoundingBox;
 }
 
-pub trait Shape: Debug + Send + Sync + Transform + Intersect + DynPartialEq {
+pub trait Shape: Debug + Send + Sync + Transform + Intersect + Bounds + DynPartialEq {
     #[inline]
     fn normal_at(&self, point: Point) -> Vector {
         let local_point = self.transformation_inverse() * point;
         let local_normal = self.local_normal_at(local_point);
-        let world_normal = self.transformation_inverse().transpose() * local_normal;
+        return self.normal_to_world(local_normal, 0.0);
+    }
+
+    /// Returns normal at point of intersection with given u and v coordinates which happened at
+    /// given time, shapes which interpolate their normals override [Shape::local_normal_at_uv]
+    #[inline]
+    fn normal_at_uv(&self, point: Point, u: f64, v: f64, time: f64) -> Vector {
+        let local_point = self.transformation_inverse_at(time) * point;
+        let local_normal = self.local_normal_at_uv(local_point, u, v);
+        return self.normal_to_world(local_normal, time);
+    }
+
+    #[inline]
+    fn normal_to_world(&self, local_normal: Vector, time: f64) -> Vector {
+        let world_normal = self.transformation_inverse_at(time).transpose() * local_normal;
         return world_normal.normalized();
     }
 
     fn local_normal_at(&self, point: Point) -> Vector;
 
+    fn local_normal_at_uv(&self, point: Point, _u: f64, _v: f64) -> Vector {
+        return self.local_normal_at(point);
+    }
+
+    /// Returns box enclosing shape in world space during whole exposure
+    fn bounds(&self) -> BoundingBox {
+        let bounds = self.local_bounds().transform(&self.transformation());
+        return self.motion().sweep(&bounds);
+    }
+
     fn material(&self) -> &Material;
+
+    fn motion(&self) -> &Motion;
+
+    fn set_motion(&mut self, motion: Motion);
+
+    /// Returns whether given shape is this shape or one of its descendants
+    fn includes(&self, shape: &dyn Shape) -> bool {
+        return core::ptr::addr_eq(self, shape);
+    }
+}
+
+/// Returns normal in object space of composite shape which folds its [Transformation] into its
+/// children, the normal is computed by child whose bounds are the nearest to [Point] and brought
+/// back to object space, composite shape without children has no surface and returns
+/// [Vector::UP]
+pub(crate) fn composite_local_normal_at<'shape>(
+    transformation: Transformation,
+    children: impl IntoIterator<Item = &'shape dyn Shape>,
+    point: Point,
+) -> Vector {
+    let point = transformation * point;
+    let child = children.into_iter().min_by(|child_1, child_2| {
+        return child_1
+            .bounds()
+            .distance_to(point)
+            .total_cmp(&child_2.bounds().distance_to(point));
+    });
+    return match child {
+        Some(child) => transformation.transpose() * child.normal_at(point),
+        None => Vector::UP,
+    };
 }
 
 impl PartialEq for dyn Shape {
@@ -47,7 +122,7 @@ impl PartialEq<&Self> for Box<dyn Shape> {
 mod tests {
     use crate::composites::Material;
     use crate::patterns::{ComplexPattern, GradientPattern, RingPattern};
-    use crate::primitives::Color;
+    use crate::primitives::{Color, transformations};
     use crate::shapes::Sphere;
     use std::sync::Arc;
 
@@ -68,6 +143,17 @@ mod tests {
         assert_eq!(shape.material(), &material);
     }
 
+    #[test]
+    fn transforming_shape_bounds_into_world_space() {
+        let shape = Sphere::new(
+            Material::default(),
+            transformations::translation(1, -3, 5) * transformations::scaling(0.5, 2, 4),
+        );
+        let bounds = shape.bounds();
+        assert_eq!(bounds.min, Point::new(0.5, -5, 1));
+        assert_eq!(bounds.max, Point::new(1.5, -1, 9));
+    }
+
     #[test]
     fn compare_dyn_shapes() {
         let mut sphere_1 = Sphere::default();
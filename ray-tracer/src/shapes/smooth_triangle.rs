@@ -0,0 +1,204 @@
+use super::triangle::intersect_triangle;
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
+use crate::primitives::{Point, Transformation, Vector};
+use crate::utils::CoarseEq;
+use core::fmt::{Debug, Display, Formatter, Result};
+
+/// Triangle with normal specified at each vertex, normal at intersection is interpolated from
+/// them which makes meshes look smooth
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmoothTriangle {
+    pub material: Material,
+    transformation_inverse: Transformation,
+    pub motion: Motion,
+    pub vertex_1: Point,
+    pub vertex_2: Point,
+    pub vertex_3: Point,
+    pub normal_1: Vector,
+    pub normal_2: Vector,
+    pub normal_3: Vector,
+    pub edge_1: Vector,
+    pub edge_2: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(
+        vertex_1: Point,
+        vertex_2: Point,
+        vertex_3: Point,
+        normal_1: Vector,
+        normal_2: Vector,
+        normal_3: Vector,
+    ) -> Self {
+        return Self {
+            material: Material::default(),
+            transformation_inverse: Transformation::IDENTITY,
+            motion: Motion::NONE,
+            vertex_1,
+            vertex_2,
+            vertex_3,
+            normal_1,
+            normal_2,
+            normal_3,
+            edge_1: vertex_2 - vertex_1,
+            edge_2: vertex_3 - vertex_1,
+        };
+    }
+}
+
+impl Intersect for SmoothTriangle {
+    fn local_intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
+        if let Some((distance, u, v)) =
+            intersect_triangle(ray, self.vertex_1, self.edge_1, self.edge_2)
+        {
+            intersections.push(Intersection::with_uv(distance, self, u, v));
+        }
+    }
+}
+
+impl Transform for SmoothTriangle {
+    fn transformation(&self) -> Transformation {
+        return self.transformation_inverse.inverse();
+    }
+
+    fn set_transformation(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation.inverse();
+    }
+
+    fn transformation_inverse(&self) -> Transformation {
+        return self.transformation_inverse;
+    }
+
+    fn set_transformation_inverse(&mut self, transformation: Transformation) {
+        self.transformation_inverse = transformation;
+    }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
+}
+
+impl Bounds for SmoothTriangle {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::from_points([self.vertex_1, self.vertex_2, self.vertex_3]);
+    }
+}
+
+impl Shape for SmoothTriangle {
+    /// Without barycentric coordinates normal of the first vertex is used
+    fn local_normal_at(&self, point: Point) -> Vector {
+        return self.local_normal_at_uv(point, 0.0, 0.0);
+    }
+
+    fn local_normal_at_uv(&self, _: Point, u: f64, v: f64) -> Vector {
+        return self.normal_2 * u + self.normal_3 * v + self.normal_1 * (1.0 - u - v);
+    }
+
+    fn material(&self) -> &Material {
+        return &self.material;
+    }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
+}
+
+impl Display for SmoothTriangle {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        return formatter
+            .debug_struct("SmoothTriangle")
+            .field("p1", &self.vertex_1)
+            .field("p2", &self.vertex_2)
+            .field("p3", &self.vertex_3)
+            .field("n1", &self.normal_1)
+            .field("n2", &self.normal_2)
+            .field("n3", &self.normal_3)
+            .field("material", &self.material)
+            .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
+            .finish();
+    }
+}
+
+impl CoarseEq for SmoothTriangle {
+    fn coarse_eq(&self, rhs: &Self) -> bool {
+        return std::ptr::eq(self, rhs)
+            || self.material == rhs.material
+                && self
+                    .transformation_inverse
+                    .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
+                && self.vertex_1.coarse_eq(&rhs.vertex_1)
+                && self.vertex_2.coarse_eq(&rhs.vertex_2)
+                && self.vertex_3.coarse_eq(&rhs.vertex_3)
+                && self.normal_1.coarse_eq(&rhs.normal_1)
+                && self.normal_2.coarse_eq(&rhs.normal_2)
+                && self.normal_3.coarse_eq(&rhs.normal_3);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::composites::Ray;
+
+    fn default_smooth_triangle() -> SmoothTriangle {
+        return SmoothTriangle::new(
+            Point::new(0, 1, 0),
+            Point::new(-1, 0, 0),
+            Point::new(1, 0, 0),
+            Vector::UP,
+            Vector::LEFT,
+            Vector::RIGHT,
+        );
+    }
+
+    #[test]
+    fn creating_smooth_triangle() {
+        let triangle = default_smooth_triangle();
+        assert_eq!(triangle.vertex_1, Point::new(0, 1, 0));
+        assert_eq!(triangle.vertex_2, Point::new(-1, 0, 0));
+        assert_eq!(triangle.vertex_3, Point::new(1, 0, 0));
+        assert_eq!(triangle.normal_1, Vector::UP);
+        assert_eq!(triangle.normal_2, Vector::LEFT);
+        assert_eq!(triangle.normal_3, Vector::RIGHT);
+    }
+
+    #[test]
+    fn intersection_with_smooth_triangle_stores_uv() {
+        let triangle = default_smooth_triangle();
+        let ray = Ray::new(Point::new(-0.2, 0.3, -2), Vector::FORWARD);
+        let mut intersections = Intersections::new();
+        triangle.local_intersect(&ray, &mut intersections);
+        assert!(intersections[0].u.coarse_eq(&0.45));
+        assert!(intersections[0].v.coarse_eq(&0.25));
+    }
+
+    #[test]
+    fn smooth_triangle_interpolates_normal() {
+        let triangle = default_smooth_triangle();
+        let normal = triangle.normal_at_uv(Point::ORIGIN, 0.45, 0.25, 0.0);
+        assert!(normal.coarse_eq(&Vector::new(-0.5547001962252291, 0.8320502943378437, 0)));
+    }
+
+    #[test]
+    fn preparing_normal_on_smooth_triangle() {
+        let triangle = default_smooth_triangle();
+        let intersection = Intersection::with_uv(1, &triangle, 0.45, 0.25);
+        let ray = Ray::new(Point::new(-0.2, 0.3, -2), Vector::FORWARD);
+        let computed_hit =
+            intersection.prepare_computations(&ray, &Intersections::from(intersection.clone()));
+        assert!(computed_hit.normal.coarse_eq(&Vector::new(
+            -0.5547001962252291,
+            0.8320502943378437,
+            0
+        )));
+    }
+}
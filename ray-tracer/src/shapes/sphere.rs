@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::{CoarseEq, solve_quadratic};
 use core::fmt::{Display, Formatter, Result};
@@ -8,6 +8,7 @@ use core::fmt::{Display, Formatter, Result};
 pub struct Sphere {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
 }
 
 impl Sphere {
@@ -15,6 +16,7 @@ impl Sphere {
         return Self {
             material,
             transformation_inverse: transformation.inverse(),
+            motion: Motion::NONE,
         };
     }
 }
@@ -35,6 +37,12 @@ impl Transform for Sphere {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
 }
 
 impl Intersect for Sphere {
@@ -53,6 +61,12 @@ impl Intersect for Sphere {
     }
 }
 
+impl Bounds for Sphere {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::new(Point::new(-1, -1, -1), Point::new(1, 1, 1));
+    }
+}
+
 impl Shape for Sphere {
     fn local_normal_at(&self, point: Point) -> Vector {
         return Vector::new(point.x, point.y, point.z);
@@ -61,6 +75,14 @@ impl Shape for Sphere {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Default for Sphere {
@@ -75,6 +97,7 @@ impl Display for Sphere {
             .debug_struct("Sphere")
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -85,7 +108,8 @@ impl CoarseEq for Sphere {
             || self.material == rhs.material
             || self
                 .transformation_inverse
-                .coarse_eq(&rhs.transformation_inverse);
+                .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion);
     }
 }
 
@@ -191,4 +215,18 @@ mod tests {
         sphere.material = material.clone();
         assert_eq!(sphere.material, material);
     }
+
+    #[test]
+    fn normal_on_moving_sphere() {
+        let sphere = Sphere {
+            motion: Motion::new(Vector::new(4, 0, 0), Vector::ZERO, Point::ORIGIN),
+            ..Sphere::default()
+        };
+        let normal = sphere.normal_at_uv(Point::new(3, 0, 0), 0.0, 0.0, 0.5);
+        assert_eq!(normal, Vector::RIGHT);
+        assert_eq!(
+            sphere.bounds(),
+            BoundingBox::new(Point::new(-1, -1, -1), Point::new(5, 1, 1))
+        );
+    }
 }
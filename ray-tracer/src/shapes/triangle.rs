@@ -1,5 +1,5 @@
-use super::{Intersect, Shape, Transform};
-use crate::composites::{Intersection, Intersections, Material, Ray};
+use super::{Bounds, Intersect, Shape, Transform};
+use crate::composites::{BoundingBox, Intersection, Intersections, Material, Motion, Ray};
 use crate::consts::EPSILON;
 use crate::primitives::{Point, Transformation, Vector};
 use crate::utils::CoarseEq;
@@ -9,6 +9,7 @@ use core::fmt::{Debug, Display, Formatter, Result};
 pub struct Triangle {
     pub material: Material,
     transformation_inverse: Transformation,
+    pub motion: Motion,
     pub vertex_1: Point,
     pub vertex_2: Point,
     pub vertex_3: Point,
@@ -25,6 +26,7 @@ impl Triangle {
         return Self {
             material: Material::default(),
             transformation_inverse: Transformation::IDENTITY,
+            motion: Motion::NONE,
             vertex_1,
             vertex_2,
             vertex_3,
@@ -37,25 +39,41 @@ impl Triangle {
 
 impl Intersect for Triangle {
     fn local_intersect<'shape>(&'shape self, ray: &Ray, intersections: &mut Intersections<'shape>) {
-        let direction_cross_edge2 = ray.direction.cross(&self.edge_2);
-        let determinant = self.edge_1.dot(&direction_cross_edge2);
-        if determinant.abs() < EPSILON {
-            return;
-        }
-        let vertex1_to_origin = ray.origin - self.vertex_1;
-        let u = vertex1_to_origin.dot(&direction_cross_edge2) / determinant;
-        if !(0.0..=1.0).contains(&u) {
-            return;
-        }
-        let origin_cross_edge1 = vertex1_to_origin.cross(&self.edge_1);
-        let v = ray.direction.dot(&origin_cross_edge1) / determinant;
-        if v > 0.0 && u + v < 1.0 {
-            let distance = self.edge_2.dot(&origin_cross_edge1) / determinant;
-            intersections.push(Intersection::new(distance, self));
+        if let Some((distance, u, v)) =
+            intersect_triangle(ray, self.vertex_1, self.edge_1, self.edge_2)
+        {
+            intersections.push(Intersection::with_uv(distance, self, u, v));
         }
     }
 }
 
+/// Möller–Trumbore ray triangle intersection, returns distance to hit and its barycentric
+/// coordinates
+pub(super) fn intersect_triangle(
+    ray: &Ray,
+    vertex_1: Point,
+    edge_1: Vector,
+    edge_2: Vector,
+) -> Option<(f64, f64, f64)> {
+    let direction_cross_edge2 = ray.direction.cross(&edge_2);
+    let determinant = edge_1.dot(&direction_cross_edge2);
+    if determinant.abs() < EPSILON {
+        return None;
+    }
+    let vertex1_to_origin = ray.origin - vertex_1;
+    let u = vertex1_to_origin.dot(&direction_cross_edge2) / determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let origin_cross_edge1 = vertex1_to_origin.cross(&edge_1);
+    let v = ray.direction.dot(&origin_cross_edge1) / determinant;
+    if v > 0.0 && u + v < 1.0 {
+        let distance = edge_2.dot(&origin_cross_edge1) / determinant;
+        return Some((distance, u, v));
+    }
+    return None;
+}
+
 impl Transform for Triangle {
     fn transformation(&self) -> Transformation {
         return self.transformation_inverse.inverse();
@@ -72,6 +90,18 @@ impl Transform for Triangle {
     fn set_transformation_inverse(&mut self, transformation: Transformation) {
         self.transformation_inverse = transformation;
     }
+
+    fn transformation_inverse_at(&self, time: f64) -> Transformation {
+        return self
+            .motion
+            .transformation_inverse_at(&self.transformation_inverse, time);
+    }
+}
+
+impl Bounds for Triangle {
+    fn local_bounds(&self) -> BoundingBox {
+        return BoundingBox::from_points([self.vertex_1, self.vertex_2, self.vertex_3]);
+    }
 }
 
 impl Shape for Triangle {
@@ -82,6 +112,14 @@ impl Shape for Triangle {
     fn material(&self) -> &Material {
         return &self.material;
     }
+
+    fn motion(&self) -> &Motion {
+        return &self.motion;
+    }
+
+    fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+    }
 }
 
 impl Display for Triangle {
@@ -96,6 +134,7 @@ impl Display for Triangle {
             .field("normal", &self.normal)
             .field("material", &self.material)
             .field("transformation", &self.transformation())
+            .field("motion", &self.motion)
             .finish();
     }
 }
@@ -107,6 +146,7 @@ impl CoarseEq for Triangle {
                 && self
                     .transformation_inverse
                     .coarse_eq(&rhs.transformation_inverse)
+                && self.motion.coarse_eq(&rhs.motion)
                 && self.vertex_1.coarse_eq(&rhs.vertex_1)
                 && self.vertex_2.coarse_eq(&rhs.vertex_2)
                 && self.vertex_3.coarse_eq(&rhs.vertex_3)
@@ -221,4 +261,16 @@ mod tests {
         assert_eq!(intersections.len(), 1);
         assert_eq!(intersections[0].distance, 2.0);
     }
+
+    #[test]
+    fn triangle_bounds() {
+        let triangle = Triangle::new(
+            Point::new(-3, 7, 2),
+            Point::new(6, 2, -4),
+            Point::new(2, -1, -1),
+        );
+        let bounds = triangle.local_bounds();
+        assert_eq!(bounds.min, Point::new(-3, -1, -4));
+        assert_eq!(bounds.max, Point::new(6, 7, 2));
+    }
 }
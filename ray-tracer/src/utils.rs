@@ -1,6 +1,6 @@
 use crate::consts::EPSILON;
-use crate::primitives::Color;
 use crate::primitives::transformations;
+use crate::primitives::{Color, Point};
 use crate::shapes::{Sphere, Transform};
 use core::ops::Mul;
 
@@ -56,6 +56,39 @@ pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
     return Some((solution_1, solution_2));
 }
 
+/// Deterministic pseudo random number generator (SplitMix64), the same seed always produces the
+/// same sequence, which keeps renders reproducible and independent of thread scheduling
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Random(u64);
+
+impl Random {
+    pub const fn new(seed: u64) -> Self {
+        return Self(seed);
+    }
+
+    /// Creates generator seeded with coordinates of [Point] and additional value
+    pub fn from_point(point: &Point, value: u64) -> Self {
+        let seed = point.x.to_bits()
+            ^ point.y.to_bits().rotate_left(21)
+            ^ point.z.to_bits().rotate_left(42)
+            ^ value.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        return Self::new(seed);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.0;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return value ^ (value >> 31);
+    }
+
+    /// Returns value in range [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+    }
+}
+
 pub fn world_default_sphere_1() -> Sphere {
     let mut sphere = Sphere::default();
     sphere.material.color = Color::new(0.8, 1, 0.6);
@@ -69,3 +102,28 @@ pub fn world_default_sphere_2() -> Sphere {
     sphere.set_transformation(transformations::scaling(0.5, 0.5, 0.5));
     return sphere;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_is_deterministic() {
+        let mut random_1 = Random::new(42);
+        let mut random_2 = Random::new(42);
+        for _ in 0..10 {
+            assert_eq!(random_1.next_u64(), random_2.next_u64());
+        }
+        let mut random_3 = Random::new(43);
+        assert_ne!(random_1.next_u64(), random_3.next_u64());
+    }
+
+    #[test]
+    fn random_floats_are_in_unit_range() {
+        let mut random = Random::from_point(&Point::new(1, -2, 3), 7);
+        for _ in 0..1000 {
+            let value = random.next_f64();
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+}